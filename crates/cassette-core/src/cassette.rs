@@ -1,5 +1,11 @@
 #[cfg(feature = "ui")]
-use std::{any::Any, cell::RefCell, collections::BTreeMap, ops, rc::Rc};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    ops,
+    rc::Rc,
+};
 use std::{
     borrow::Borrow,
     cmp,
@@ -135,6 +141,7 @@ impl<Component> Cassette<Component> {
 #[derive(Debug)]
 pub struct CassetteState {
     root: RootCassetteState,
+    scope: CassetteScope,
 }
 
 #[cfg(feature = "ui")]
@@ -142,11 +149,88 @@ impl CassetteState {
     pub fn new(id: Uuid, trigger: UseForceUpdateHandle) -> Self {
         Self {
             root: RootCassetteState::new(id, trigger),
+            scope: CassetteScope::default(),
+        }
+    }
+
+    fn get<T>(&self, name: &str) -> Result<Option<T>, String>
+    where
+        T: DeserializeOwned,
+    {
+        match self.scope.levels.last() {
+            Some(level) => self.root.get_scoped(&level.task_path(name)),
+            None => self.root.get_child(name),
+        }
+    }
+
+    fn get_data(&self, key: &str) -> Result<::serde_json::Value, String> {
+        let (name, rest) = split_task_key(key);
+
+        // NOTE: the nested tasks shadow the outer ones of the same name, the innermost first
+        match self
+            .scope
+            .levels
+            .iter()
+            .rev()
+            .find(|level| level.names.contains(&name))
+        {
+            Some(level) => self
+                .root
+                .get_scoped_data(&level.task_path(&name), rest)
+                .ok_or_else(|| format!("no such key: {key}")),
+            None => self.root.get_data(key),
         }
     }
 
     fn set(&mut self, name: &str, value: crate::task::TaskSpec) {
-        self.root.set_child(name, value)
+        match self.scope.levels.last() {
+            Some(level) => self.root.set_scoped(&level.task_path(name), value),
+            None => self.root.set_child(name, value),
+        }
+    }
+
+    fn task_path(&self, name: &str) -> crate::task::TaskPath {
+        match self.scope.levels.last() {
+            Some(level) => level.task_path(name),
+            None => crate::task::TaskPath::default().key(name),
+        }
+    }
+}
+
+/// Split the JSON pointer of the task data into the task name and the rest.
+#[cfg(feature = "ui")]
+fn split_task_key(key: &str) -> (String, &str) {
+    let key = key.strip_prefix('/').unwrap_or(key);
+    let (name, rest) = match key.find('/') {
+        Some(index) => key.split_at(index),
+        None => (key, ""),
+    };
+    // Please see: https://datatracker.ietf.org/doc/html/rfc6901#section-4
+    (name.replace("~1", "/").replace("~0", "~"), rest)
+}
+
+#[cfg(feature = "ui")]
+#[derive(Clone, Debug, Default)]
+struct CassetteScope {
+    /// The nested task states, the innermost last
+    levels: Vec<CassetteScopeLevel>,
+    /// Variables bound to the nested tasks, such as `~/item`
+    vars: crate::task::TaskSpec,
+}
+
+#[cfg(feature = "ui")]
+#[derive(Clone, Debug)]
+struct CassetteScopeLevel {
+    /// The path of the nested task states
+    path: crate::task::TaskPath,
+    /// The names of the nested tasks, which shadow the outer ones
+    names: BTreeSet<String>,
+}
+
+#[cfg(feature = "ui")]
+impl CassetteScopeLevel {
+    fn task_path(&self, name: &str) -> crate::task::TaskPath {
+        self.path.clone().key(name)
     }
 }
 
@@ -188,6 +272,10 @@ impl RootCassetteState {
         Self { trigger }
     }
 
+    fn id(&self) -> Option<Uuid> {
+        Self::ID.with_borrow(|id| *id)
+    }
+
     fn update(&self, trigger: bool) {
         if trigger {
             self.trigger.force_update()
@@ -212,6 +300,34 @@ impl RootCassetteState {
         Self::SPEC.with_borrow(|spec| spec.get(key).cloned())
     }
 
+    fn get_scoped<T>(&self, path: &crate::task::TaskPath) -> Result<Option<T>, String>
+    where
+        T: DeserializeOwned,
+    {
+        Self::SPEC.with_borrow(|spec| {
+            spec.get_at(path)
+                .map(|value| {
+                    ::serde_json::from_value(value.clone())
+                        .map_err(|error| format!("Failed to decode task state: {error}"))
+                })
+                .transpose()
+        })
+    }
+
+    fn get_scoped_data(
+        &self,
+        path: &crate::task::TaskPath,
+        key: &str,
+    ) -> Option<::serde_json::Value> {
+        Self::SPEC.with_borrow(|spec| {
+            let value = spec.get_at(path)?;
+            match key {
+                "" | "/" => Some(value.clone()),
+                key => value.pointer(key).cloned(),
+            }
+        })
+    }
+
     fn set_child(&self, name: &str, value: crate::task::TaskSpec) {
         Self::SPEC.with_borrow_mut(|spec| {
             if spec.set_child(name, value) {
@@ -221,6 +337,15 @@ impl RootCassetteState {
         })
     }
 
+    fn set_scoped(&self, path: &crate::task::TaskPath, value: crate::task::TaskSpec) {
+        Self::SPEC.with_borrow_mut(|spec| {
+            if spec.set(path, value) {
+                info!("Detected child update: {path}");
+                self.update(false);
+            }
+        })
+    }
+
     fn set_handler<T>(&self, id: (String, String), value: T, trigger: bool)
     where
         T: 'static,
@@ -286,15 +411,69 @@ impl<'a> CassetteContext<'a> {
         Self { state, task }
     }
 
-    pub(crate) fn get_child<T>(&self) -> Result<Option<T>, String>
+    pub const fn task(&self) -> &'a crate::task::CassetteTask {
+        self.task
+    }
+
+    pub fn get_child<T>(&self) -> Result<Option<T>, String>
     where
         T: DeserializeOwned,
     {
-        self.state.root.get_child(&self.task.name)
+        self.state.get(&self.task.name)
     }
 
     pub(crate) fn get_data(&self, key: &str) -> Result<::serde_json::Value, String> {
-        self.state.root.get_data(key)
+        self.state.get_data(key)
+    }
+
+    /// Replace all expressions in the given value.
+    pub fn eval(&self, value: &::serde_json::Value) -> Result<::serde_json::Value, String> {
+        use serde_json::Value;
+
+        match value {
+            Value::Null => Ok(Value::Null),
+            Value::Bool(data) => Ok(Value::Bool(*data)),
+            Value::Number(data) => Ok(Value::Number(data.clone())),
+            // NOTE: the invalid expressions are reported by the validator, so keep them as they are
+            Value::String(data) => match data.parse::<crate::expr::Template>() {
+                Ok(template) => template.eval(self),
+                Err(_) => Ok(Value::String(data.clone())),
+            },
+            Value::Array(array) => array
+                .iter()
+                .map(|value| self.eval(value))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| self.eval(value).map(|value| (key.clone(), value)))
+                .collect::<Result<_, _>>()
+                .map(Value::Object),
+        }
+    }
+
+    /// Create a nested state of the given tasks, which are stored under the given path.
+    ///
+    /// The nested tasks can refer to the given variables with `~/`.
+    pub fn scoped(
+        &self,
+        path: &crate::task::TaskPath,
+        tasks: &[crate::task::CassetteTask],
+        vars: ::serde_json::Map<String, ::serde_json::Value>,
+    ) -> CassetteState {
+        let mut scope = self.state.scope.clone();
+        scope.levels.push(CassetteScopeLevel {
+            path: self.state.task_path(&self.task.name).join(path),
+            names: tasks.iter().map(|task| task.name.clone()).collect(),
+        });
+        if let ::serde_json::Value::Object(map) = &mut scope.vars.0 {
+            map.extend(vars);
+        }
+
+        CassetteState {
+            root: self.state.root.clone(),
+            scope,
+        }
     }
 
     pub(crate) fn set(self, state: crate::task::TaskState) -> crate::task::TaskState<()> {
@@ -319,6 +498,24 @@ impl<'a> CassetteContext<'a> {
         }
     }
 
+    /// Evaluate the task guard (`metadata.when`), if any.
+    pub fn is_enabled(&self) -> Result<bool, String> {
+        match self.task.metadata.when.as_deref() {
+            Some(when) => when
+                .parse::<crate::expr::Expr>()
+                .and_then(|expr| expr.eval_bool(self))
+                .map_err(|error| format!("Failed to evaluate task guard: {error}")),
+            None => Ok(true),
+        }
+    }
+
+    /// Skip the task, clearing its stale state.
+    pub fn skip(self) -> crate::task::TaskState<()> {
+        self.set(crate::task::TaskState::Skip {
+            state: Some(crate::task::TaskSpec::default()),
+        })
+    }
+
     pub fn set_task_state(self, value: crate::task::TaskSpec) {
         self.state.set(&self.task.name, value)
     }
@@ -332,13 +529,90 @@ impl<'a> CassetteContext<'a> {
     where
         T: Any,
     {
-        let task_name = self.task.name.clone();
+        let task_name = self.state.task_path(&self.task.name).to_string();
         let handler_name = id.into();
         let id = (task_name, handler_name);
         CassetteTaskHandle {
             root: self.state.root.clone(),
             id: id.clone(),
             item: RootCassetteState::use_handler(&self.state.root, force_init, id.clone(), f_init),
+            persist: None,
+        }
+    }
+
+    /// Like [`Self::use_state`], but the value can be kept in the local storage
+    /// and the URL query, if the task opts in (`metadata.persist` and `metadata.query`).
+    pub fn use_persistent_state<T>(
+        &self,
+        id: impl Into<String>,
+        force_init: bool,
+        f_init: impl FnOnce() -> T,
+    ) -> CassetteTaskHandle<T>
+    where
+        T: 'static + Serialize + DeserializeOwned,
+    {
+        self.use_persistent_state_with(id, force_init, f_init, |value: T| value)
+    }
+
+    /// Like [`Self::use_persistent_state`], but the persisted value is decoded as `R` and converted,
+    /// such as to check it against the task inputs, as the URL query cannot be trusted.
+    pub fn use_persistent_state_with<T, R>(
+        &self,
+        id: impl Into<String>,
+        force_init: bool,
+        f_init: impl FnOnce() -> T,
+        f_load: impl FnOnce(R) -> T,
+    ) -> CassetteTaskHandle<T>
+    where
+        T: 'static + Serialize,
+        R: DeserializeOwned,
+    {
+        let handler_name = id.into();
+        let persist = self.persist_key(&handler_name).map(|key| {
+            Rc::new(Persistence {
+                key,
+                encode: |value| ::serde_json::to_value(value).unwrap_or_default(),
+            })
+        });
+
+        let f_init = || {
+            persist
+                .as_ref()
+                .and_then(|persist| persist.key.load())
+                .map(f_load)
+                .unwrap_or_else(f_init)
+        };
+        CassetteTaskHandle {
+            persist: persist.clone(),
+            ..self.use_state(handler_name, force_init, f_init)
+        }
+    }
+
+    /// Load the persisted value, if the task opts in.
+    pub fn get_persistent_state<T>(&self, id: &str) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.persist_key(id).and_then(|key| key.load())
+    }
+
+    fn persist_key(&self, handler_name: &str) -> Option<crate::persist::PersistKey> {
+        let id = self.state.root.id()?;
+        let task_path = self.state.task_path(&self.task.name).to_string();
+        crate::persist::PersistKey::new(id, &self.task.metadata, &task_path, handler_name)
+    }
+}
+
+#[cfg(feature = "ui")]
+impl crate::expr::ExprContext for CassetteContext<'_> {
+    fn get_data(&self, key: &str) -> Result<::serde_json::Value, String> {
+        self.state.get_data(key)
+    }
+
+    fn get_spec(&self, key: &str) -> Result<::serde_json::Value, String> {
+        match self.state.scope.vars.try_get(key) {
+            Some(value) => Ok(value.clone()),
+            None => self.task.spec.get(key).cloned(),
         }
     }
 }
@@ -386,6 +660,21 @@ pub struct CassetteTaskHandle<T> {
     root: RootCassetteState,
     id: (String, String),
     item: Rc<T>,
+    persist: Option<Rc<Persistence<T>>>,
+}
+
+#[cfg(feature = "ui")]
+#[derive(Debug)]
+struct Persistence<T> {
+    key: crate::persist::PersistKey,
+    encode: fn(&T) -> ::serde_json::Value,
+}
+
+#[cfg(feature = "ui")]
+impl<T> Persistence<T> {
+    fn store(&self, value: &T) {
+        self.key.store(&(self.encode)(value))
+    }
 }
 
 #[cfg(feature = "ui")]
@@ -404,6 +693,7 @@ impl<T> Clone for CassetteTaskHandle<T> {
             root: self.root.clone(),
             id: self.id.clone(),
             item: self.item.clone(),
+            persist: self.persist.clone(),
         }
     }
 }
@@ -446,6 +736,9 @@ impl<T> GenericCassetteTaskHandle<T> for CassetteTaskHandle<T> {
     where
         T: 'static,
     {
+        if let Some(persist) = &self.persist {
+            persist.store(&value);
+        }
         RootCassetteState::set_handler(&self.root, self.id.clone(), value, true)
     }
 }
@@ -491,6 +784,21 @@ impl<T> CassetteTaskHandle<Vec<T>> {
     }
 }
 
+#[cfg(feature = "ui")]
+impl CassetteTaskHandle<crate::data::selection::DataTableSelection> {
+    pub fn get_item(&self, index: usize) -> bool {
+        self.item.contains(index)
+    }
+
+    pub fn set_item(&self, index: usize, selected: bool) {
+        if self.get_item(index) != selected {
+            let mut selection = (*self.item).clone();
+            selection.set(index, selected);
+            self.set(selection)
+        }
+    }
+}
+
 #[cfg(feature = "ui")]
 impl CassetteTaskHandle<::serde_json::Value> {
     pub fn get_item(&self, path: &crate::data::actor::SchemaPath) -> &::serde_json::Value {
@@ -545,6 +853,9 @@ impl<T> GenericCassetteTaskHandle<T> for CassetteLazyHandle<T> {
     where
         T: 'static,
     {
+        if let Some(persist) = &self.0.persist {
+            persist.store(&value);
+        }
         RootCassetteState::set_handler(&self.0.root, self.0.id.clone(), value, false)
     }
 }
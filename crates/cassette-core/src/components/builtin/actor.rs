@@ -0,0 +1,76 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+use crate::data::{actor::SchemaActor, table::DataTable};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    #[serde(default)]
+    pub base_url: Option<String>,
+    pub uri: String,
+    /// The query of the schema request, such as the chart of the helm actor
+    #[serde(default)]
+    pub query: BTreeMap<String, String>,
+    #[serde(default)]
+    pub schema: Rc<SchemaActor>,
+    #[serde(default)]
+    pub default: Value,
+    #[serde(default)]
+    pub table: Option<Rc<DataTable>>,
+
+    #[serde(default = "Spec::default_primary_key")]
+    pub primary_key: String,
+
+    #[serde(default = "Spec::default_label_create")]
+    pub label_create: String,
+    #[serde(default = "Spec::default_label_delete")]
+    pub label_delete: String,
+    #[serde(default = "Spec::default_label_update")]
+    pub label_update: String,
+
+    #[serde(default = "Spec::default_create")]
+    pub create: bool,
+
+    #[serde(default = "Spec::default_delete")]
+    pub delete: bool,
+
+    #[serde(default = "Spec::default_update")]
+    pub update: bool,
+}
+
+impl Spec {
+    fn default_primary_key() -> String {
+        "id".into()
+    }
+
+    fn default_label_create() -> String {
+        "Create".into()
+    }
+
+    fn default_label_delete() -> String {
+        "Delete".into()
+    }
+
+    fn default_label_update() -> String {
+        "Update".into()
+    }
+
+    const fn default_create() -> bool {
+        true
+    }
+
+    const fn default_delete() -> bool {
+        true
+    }
+
+    const fn default_update() -> bool {
+        true
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+use crate::data::{export::DataExportType, table::DataTable};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    /// The data table or any task state to export
+    pub data: ExportInput,
+
+    /// The name of the downloaded file, which defaults to the table's one
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    #[serde(default = "Spec::default_label")]
+    pub label: String,
+
+    /// The file type, which defaults to `CSV` for tables and `JSON` for the others
+    #[serde(default)]
+    pub r#type: Option<DataExportType>,
+}
+
+/// A data table, or any other value such as a task state.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ExportInput {
+    Table(DataTable),
+    Value(Value),
+}
+
+impl Spec {
+    fn default_label() -> String {
+        "Download".into()
+    }
+
+    pub fn export_type(&self) -> DataExportType {
+        self.r#type.unwrap_or(match &self.data {
+            ExportInput::Table(_) => DataExportType::Csv,
+            ExportInput::Value(_) => DataExportType::Json,
+        })
+    }
+
+    /// The name of the downloaded file, including its extension.
+    pub fn filename(&self) -> String {
+        let extension = self.export_type().extension();
+        let filename = match (&self.filename, &self.data) {
+            (Some(filename), _) => filename.as_str(),
+            // NOTE: the table may be named after the uploaded file, such as `data.csv`
+            (None, ExportInput::Table(table)) if !table.name.is_empty() => table
+                .name
+                .rsplit_once('.')
+                .map(|(stem, _)| stem)
+                .filter(|stem| !stem.is_empty())
+                .unwrap_or(&table.name),
+            (None, _) => "data",
+        };
+
+        if filename.ends_with(extension) {
+            filename.into()
+        } else {
+            format!("{filename}{extension}")
+        }
+    }
+
+    /// Serialize the data into the file contents.
+    pub fn export(&self) -> Result<Vec<u8>> {
+        let r#type = self.export_type();
+        match &self.data {
+            ExportInput::Table(table) => r#type.export_table(&table.name, &table.data),
+            ExportInput::Value(value) => r#type.export_value(value),
+        }
+    }
+}
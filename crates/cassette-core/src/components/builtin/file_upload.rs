@@ -0,0 +1,33 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+use crate::data::table::{DataTableSourceOptions, DataTableSourceType};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    #[serde(default = "Spec::default_label_title")]
+    pub label_title: String,
+
+    #[serde(default)]
+    pub label_detail: Option<String>,
+
+    #[serde(default = "Spec::default_type")]
+    pub r#type: DataTableSourceType,
+
+    #[serde(default, flatten)]
+    pub options: DataTableSourceOptions,
+}
+
+impl Spec {
+    fn default_label_title() -> String {
+        "Upload".into()
+    }
+
+    const fn default_type() -> DataTableSourceType {
+        DataTableSourceType::Raw
+    }
+}
@@ -0,0 +1,21 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::task::CassetteTask;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    pub component: String,
+
+    #[serde(default)]
+    pub inputs: Map<String, Value>,
+
+    #[serde(default)]
+    pub outputs: Map<String, Value>,
+
+    /// Inlined by the loader
+    #[serde(default)]
+    pub tasks: Option<Vec<CassetteTask>>,
+}
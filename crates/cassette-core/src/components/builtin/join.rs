@@ -0,0 +1,124 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+use crate::data::{
+    join::{DataTableJoinKey, DataTableJoinKind},
+    json::JsonTable,
+    table::{DataTable, DataTableLog, DataTableLogKind, DataTableSource},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    /// The name of the output table, or the first table's one if not given
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The tables to combine, which are joined from left to right
+    pub tables: Vec<JoinInput>,
+
+    #[serde(default)]
+    pub r#type: JoinType,
+
+    /// The key columns to join the tables, which are ignored for `union`
+    #[serde(default)]
+    pub on: Vec<DataTableJoinKey>,
+}
+
+/// A data table, or an array of objects such as the `KubernetesList` contents.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum JoinInput {
+    Table(DataTable),
+    Objects(Vec<Value>),
+}
+
+impl JoinInput {
+    fn into_table(self, index: usize) -> Result<DataTable> {
+        match self {
+            Self::Table(table) => Ok(table),
+            Self::Objects(objects) => {
+                let data =
+                    DataTableSource::Json(JsonTable::from_objects(objects, &Default::default())?);
+                Ok(DataTable {
+                    name: format!("table{index}"),
+                    // NOTE: the objects have no provenance, so keep the id stable by their contents
+                    log: DataTableLog::derive(DataTableLogKind::Created, [], &data)?,
+                    data: Rc::new(data),
+                })
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum JoinType {
+    #[default]
+    Inner,
+    Left,
+    Outer,
+    Union,
+}
+
+impl Spec {
+    /// Combine the tables into a new one.
+    ///
+    /// The previous output is reused as long as neither the tables nor the spec have changed.
+    pub fn apply(self, previous: Option<&DataTable>) -> Result<DataTable> {
+        let Self {
+            name,
+            tables,
+            r#type,
+            on,
+        } = self;
+
+        let tables = tables
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| input.into_table(index))
+            .collect::<Result<Vec<_>>>()?;
+        let Some((first, rest)) = tables.split_first() else {
+            bail!("No tables to combine")
+        };
+        let name = name.unwrap_or_else(|| first.name.clone());
+
+        // NOTE: the table names are used to rename the conflicting columns
+        let names: Vec<_> = tables.iter().map(|table| &table.name).collect();
+        let log = DataTableLog::derive(
+            DataTableLogKind::Joined,
+            tables.iter().map(|table| &table.log),
+            &(&name, r#type, &on, names),
+        )?;
+        if let Some(previous) = previous.filter(|previous| previous.log.id == log.id) {
+            return Ok(previous.clone());
+        }
+
+        let data = match r#type {
+            JoinType::Inner | JoinType::Left | JoinType::Outer => {
+                let kind = match r#type {
+                    JoinType::Left => DataTableJoinKind::Left,
+                    JoinType::Outer => DataTableJoinKind::Outer,
+                    _ => DataTableJoinKind::Inner,
+                };
+                rest.iter().try_fold((*first.data).clone(), |data, table| {
+                    data.join(&table.data, &table.name, kind, &on)
+                })?
+            }
+            JoinType::Union => DataTableSource::union(tables.iter().map(|table| &*table.data))?,
+        };
+
+        Ok(DataTable {
+            name,
+            log: log.follow(previous.map(|previous| &previous.log)),
+            data: Rc::new(data),
+        })
+    }
+}
@@ -0,0 +1,24 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+use crate::data::table::{DataTableSourceOptions, DataTableSourceType};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    #[serde(default)]
+    pub base_url: Option<String>,
+    pub uri: String,
+    #[serde(default)]
+    pub query: BTreeMap<String, String>,
+    /// Parse the response body as a data file, rather than a gateway data table
+    #[serde(default)]
+    pub r#type: Option<DataTableSourceType>,
+    #[serde(default, flatten)]
+    pub options: DataTableSourceOptions,
+}
@@ -0,0 +1,40 @@
+pub mod actor;
+pub mod export;
+pub mod file_upload;
+pub mod for_each;
+pub mod include;
+pub mod join;
+pub mod load;
+pub mod table;
+pub mod text;
+pub mod text_input;
+pub mod transform;
+
+use super::registry::TaskRegistry;
+
+/// Declare the built-in task kinds, without the renderers.
+pub fn register(registry: &mut TaskRegistry) {
+    registry.register::<self::actor::Spec>("Actor");
+    registry.register::<self::export::Spec>("Export");
+    registry.register::<self::file_upload::Spec>("FileUpload");
+    registry.register::<self::for_each::Spec>("ForEach");
+    registry.register::<self::include::Spec>("Include");
+    registry.register::<self::join::Spec>("Join");
+    registry.register::<self::load::Spec>("Load");
+    // NOTE: the flattened options are buffered with the input table,
+    //       so they are checked on their own as well
+    registry
+        .register::<self::table::Spec>("Table")
+        .with_spec::<self::table::SpecOptions>()
+        .with_spec::<self::table::SpecSelection>();
+    registry.register::<self::text::Spec>("Text");
+    registry.register::<self::text_input::Spec>("TextInput");
+    registry.register::<self::transform::Spec>("Transform");
+    registry.insert("Variable");
+}
+
+pub fn registry() -> TaskRegistry {
+    let mut registry = TaskRegistry::default();
+    register(&mut registry);
+    registry
+}
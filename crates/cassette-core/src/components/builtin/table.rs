@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+use crate::data::{
+    table::DataTable,
+    view::{cell_text, DataTableView},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    pub table: DataTable,
+
+    #[serde(flatten)]
+    pub options: SpecOptions,
+}
+
+/// The options of [`Spec`], which can be evaluated apart from the input table,
+/// as the table may be too large to evaluate on every render.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecOptions {
+    /// The buttons of each row, which emit the clicked row into the task state
+    #[serde(default)]
+    pub actions: Vec<TableRowAction>,
+
+    /// The rows to select when the table is (re)loaded
+    #[serde(default)]
+    pub initial_selection: TableInitialSelection,
+
+    #[serde(default = "SpecOptions::default_label_bulk_select")]
+    pub label_bulk_select: String,
+
+    /// How to render the cells of the columns, keyed by the column names
+    #[serde(default)]
+    pub renderers: BTreeMap<String, TableCellRenderer>,
+
+    #[serde(default)]
+    pub selection: TableSelectionMode,
+
+    /// The initial view of the table, which can be changed by the users
+    #[serde(default)]
+    pub view: DataTableView,
+}
+
+impl SpecOptions {
+    fn default_label_bulk_select() -> String {
+        "All".into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRowAction {
+    /// The name of the action, which is emitted with the clicked row
+    pub name: String,
+
+    /// The label of the button, which defaults to the name
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TableSelectionMode {
+    /// The rows are not selectable, and the whole table is output
+    None,
+    /// At most one row can be selected with the radio buttons
+    Single,
+    /// Any rows can be selected with the checkboxes
+    #[default]
+    Multi,
+}
+
+impl TableSelectionMode {
+    /// Check whether the rows can be initially selected in this mode.
+    pub fn check_initial_selection(
+        self,
+        initial_selection: TableInitialSelection,
+    ) -> Result<(), String> {
+        match (self, initial_selection) {
+            (Self::Single, TableInitialSelection::All) => {
+                Err("Cannot select all rows in the single selection mode".into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TableInitialSelection {
+    #[default]
+    None,
+    /// Select the first row in the current view
+    First,
+    /// Select all the rows, which is not allowed for the `single` mode
+    All,
+}
+
+/// The selection fields of [`Spec`], which are checked together even if the table is dynamic.
+#[derive(Deserialize)]
+#[serde(try_from = "SpecSelectionFields")]
+pub(super) struct SpecSelection;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpecSelectionFields {
+    #[serde(default)]
+    initial_selection: TableInitialSelection,
+    #[serde(default)]
+    selection: TableSelectionMode,
+}
+
+impl TryFrom<SpecSelectionFields> for SpecSelection {
+    type Error = String;
+
+    fn try_from(fields: SpecSelectionFields) -> Result<Self, Self::Error> {
+        let SpecSelectionFields {
+            initial_selection,
+            selection,
+        } = fields;
+        selection
+            .check_initial_selection(initial_selection)
+            .map(|()| Self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum TableCellRenderer {
+    /// A hyperlink, whose templates can refer to the cell as `{value}`
+    /// and to the other columns of the row by their names, such as `{name}`
+    #[serde(rename_all = "camelCase")]
+    Link {
+        url: String,
+        #[serde(default)]
+        text: Option<String>,
+    },
+    /// A colored label, whose color is chosen by the cell
+    #[serde(rename_all = "camelCase")]
+    Label {
+        #[serde(default)]
+        colors: BTreeMap<String, TableCellColor>,
+        #[serde(default)]
+        default: TableCellColor,
+    },
+    #[serde(rename_all = "camelCase")]
+    Timestamp {
+        #[serde(default)]
+        relative: bool,
+    },
+    /// A number of bytes, such as `1.5 MiB`
+    #[serde(rename_all = "camelCase")]
+    Bytes {
+        #[serde(default)]
+        binary: bool,
+    },
+    /// A check or a cross icon
+    Bool,
+    /// A summary of the value, which shows the whole JSON on click
+    Json,
+    /// A progress bar of the number between `min` and `max`
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        #[serde(default)]
+        min: f64,
+        #[serde(default = "TableCellRenderer::default_progress_max")]
+        max: f64,
+    },
+}
+
+impl TableCellRenderer {
+    const fn default_progress_max() -> f64 {
+        100.0
+    }
+
+    /// Fill the template with the cell value and the row, such as `{name} ({version})`.
+    ///
+    /// The unknown names are left as they are.
+    pub fn format(template: &str, value: &Value, columns: &[String], row: &[Value]) -> String {
+        Self::format_with(template, value, columns, row, |text| text.into())
+    }
+
+    /// Fill the URL template with the cell value and the row, such as `/releases/{name}`.
+    ///
+    /// The substituted values are percent-encoded, so they cannot escape their path segments.
+    pub fn format_url(template: &str, value: &Value, columns: &[String], row: &[Value]) -> String {
+        Self::format_with(template, value, columns, row, encode_uri_component)
+    }
+
+    fn format_with(
+        template: &str,
+        value: &Value,
+        columns: &[String],
+        row: &[Value],
+        encode: impl Fn(&str) -> String,
+    ) -> String {
+        let mut formatted = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            formatted.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let Some(end) = rest.find('}') else {
+                break;
+            };
+            let name = &rest[1..end];
+            let replaced = match name {
+                "value" => Some(value),
+                name => columns
+                    .iter()
+                    .position(|column| column == name)
+                    .and_then(|index| row.get(index)),
+            };
+            match replaced {
+                Some(replaced) => formatted.push_str(&encode(&cell_text(replaced))),
+                None => formatted.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        formatted.push_str(rest);
+        formatted
+    }
+}
+
+/// Percent-encode all characters but the unreserved ones of RFC 3986.
+fn encode_uri_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            byte => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum TableCellColor {
+    Blue,
+    Cyan,
+    Gold,
+    Green,
+    #[default]
+    Grey,
+    Orange,
+    Purple,
+    Red,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn select_all_in_single_mode() {
+        let check = |value| ::serde_json::from_value::<SpecSelection>(value).map(|_| ());
+
+        assert!(check(json!({ "selection": "single", "initialSelection": "all" })).is_err());
+        for value in [
+            json!({}),
+            json!({ "selection": "single" }),
+            json!({ "selection": "single", "initialSelection": "first" }),
+            json!({ "selection": "multi", "initialSelection": "all" }),
+            json!({ "selection": "none", "initialSelection": "all" }),
+            json!({ "initialSelection": "all" }),
+        ] {
+            assert!(check(value.clone()).is_ok(), "{value}");
+        }
+    }
+
+    #[test]
+    fn format_url() {
+        let columns = ["name".to_string(), "namespace".to_string()];
+        let row = [json!("my app"), json!("a/b")];
+        assert_eq!(
+            TableCellRenderer::format_url(
+                "/ns/{namespace}/apps/{value}?q={unknown}",
+                &row[0],
+                &columns,
+                &row,
+            ),
+            "/ns/a%2Fb/apps/my%20app?q={unknown}",
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default = "Spec::default_label_submit")]
+    pub label_submit: String,
+    #[serde(default)]
+    pub placeholder: Option<String>,
+}
+
+impl Spec {
+    fn default_label_submit() -> String {
+        "Submit".into()
+    }
+}
@@ -0,0 +1,13 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::data::{table::DataTable, transform::DataTableTransform};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    pub table: DataTable,
+
+    #[serde(default, flatten)]
+    pub transform: DataTableTransform,
+}
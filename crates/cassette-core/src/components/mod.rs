@@ -1,11 +1,15 @@
 #[cfg(feature = "ui")]
 pub mod actor;
+pub mod builtin;
 #[cfg(feature = "ui")]
 pub mod error;
 #[cfg(feature = "ui")]
 pub mod loading;
+pub mod registry;
+mod schema;
 #[cfg(feature = "ui")]
 pub mod todo;
+pub mod validator;
 
 use kube::CustomResource;
 use schemars::JsonSchema;
@@ -39,11 +43,22 @@ use crate::task::CassetteTask;
 #[serde(rename_all = "camelCase")]
 pub struct CassetteComponentSpec {
     #[serde(default)]
+    #[schemars(schema_with = "CassetteComponentSpec::tasks_schema")]
     pub tasks: Vec<CassetteTask>,
 }
 
+impl CassetteComponentSpec {
+    // NOTE: the plugin task specs are still accepted, as the unknown fields are preserved
+    fn tasks_schema(gen: &mut ::schemars::gen::SchemaGenerator) -> ::schemars::schema::Schema {
+        self::builtin::registry().crd_tasks_schema(gen)
+    }
+}
+
 #[cfg(feature = "ui")]
 pub trait ComponentRenderer<Spec> {
+    /// The spec keys left unevaluated, as the renderer evaluates them by itself.
+    const RAW_KEYS: &'static [&'static str] = &[];
+
     fn render(
         self,
         ctx: &mut crate::cassette::CassetteContext,
@@ -66,46 +81,20 @@ where
     where
         Self: Sized,
     {
-        use serde_json::Value;
-
-        fn replace_key(
-            ctx: &crate::cassette::CassetteContext,
-            spec: &crate::task::TaskSpec,
-            value: &Value,
-        ) -> Result<Value, String> {
-            match value {
-                Value::Null => Ok(Value::Null),
-                Value::Bool(data) => Ok(Value::Bool(*data)),
-                Value::Number(data) => Ok(Value::Number(data.clone())),
-                Value::String(data) => {
-                    if data.starts_with(":/") {
-                        ctx.get_data(&data[1..])
-                    } else if data.starts_with("~/") {
-                        spec.get(&data[1..]).cloned()
-                    } else if data.starts_with("\\:/") || data.starts_with("\\~/") {
-                        Ok(Value::String(data[1..].into()))
-                    } else {
-                        Ok(Value::String(data.clone()))
-                    }
-                }
-                Value::Array(array) => array
-                    .iter()
-                    .map(|value| replace_key(ctx, spec, value))
-                    .collect::<Result<_, _>>()
-                    .map(Value::Array),
-                Value::Object(map) => map
-                    .iter()
-                    .map(|(key, value)| {
-                        replace_key(ctx, spec, value).map(|value| (key.clone(), value))
-                    })
-                    .collect::<Result<_, _>>()
-                    .map(Value::Object),
-            }
-        }
-
         let state = ctx.get_child()?.unwrap_or_default();
 
-        let spec = replace_key(&ctx, spec, &spec.0)?;
+        let mut spec = spec.0.clone();
+        let raw: Vec<_> = match spec.as_object_mut() {
+            Some(map) => <Self as ComponentRenderer<Spec>>::RAW_KEYS
+                .iter()
+                .filter_map(|&key| map.remove_entry(key))
+                .collect(),
+            None => Vec::default(),
+        };
+        let mut spec = ctx.eval(&spec)?;
+        if let ::serde_json::Value::Object(map) = &mut spec {
+            map.extend(raw);
+        }
         let spec = ::serde_json::from_value(spec)
             .map_err(|error| format!("Failed to parse task spec: {error}"))?;
 
@@ -0,0 +1,186 @@
+#[cfg(feature = "ui")]
+use std::{cell::RefCell, rc::Rc};
+use std::{collections::BTreeMap, fmt};
+
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{RootSchema, Schema},
+    JsonSchema,
+};
+use serde::de::DeserializeOwned;
+
+use super::validator::{CassetteValidator, SpecChecker};
+
+#[cfg(feature = "ui")]
+pub type TaskRenderFn =
+    fn(crate::cassette::CassetteContext, &crate::task::TaskSpec) -> crate::task::TaskResult<()>;
+
+type TaskSchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// A set of the available task kinds.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    kinds: BTreeMap<String, TaskKind>,
+}
+
+impl fmt::Debug for TaskRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.kinds.keys()).finish()
+    }
+}
+
+impl TaskRegistry {
+    /// Declare a task kind, or get the existing one.
+    pub fn insert(&mut self, kind: impl Into<String>) -> &mut TaskKind {
+        let name = kind.into();
+        self.kinds
+            .entry(name.clone())
+            .or_insert_with(|| TaskKind::new(name))
+    }
+
+    /// Register a task kind, whose spec is checked and described by the given type.
+    pub fn register<Spec>(&mut self, kind: impl Into<String>) -> &mut TaskKind
+    where
+        Spec: DeserializeOwned + JsonSchema,
+    {
+        self.insert(kind).with_spec::<Spec>().with_schema::<Spec>()
+    }
+
+    pub fn get(&self, kind: &str) -> Option<&TaskKind> {
+        self.kinds.get(kind)
+    }
+
+    /// List the available task kinds, ordered by name.
+    pub fn kinds(&self) -> impl Iterator<Item = &TaskKind> {
+        self.kinds.values()
+    }
+
+    /// Generate the JSON schema of the component specs, discriminated by the task kinds.
+    pub fn schema(&self) -> RootSchema {
+        super::schema::build(self)
+    }
+
+    pub(super) fn crd_tasks_schema(&self, gen: &mut SchemaGenerator) -> Schema {
+        super::schema::build_crd_tasks(self, gen)
+    }
+
+    pub fn validator(&self) -> CassetteValidator {
+        let mut validator = CassetteValidator::default();
+        for kind in self.kinds.values() {
+            validator.insert_checker(kind.name.clone(), kind.checks.clone());
+        }
+        validator
+    }
+}
+
+#[cfg(feature = "ui")]
+impl TaskRegistry {
+    thread_local! {
+        static CURRENT: RefCell<Rc<TaskRegistry>> = Default::default();
+    }
+
+    /// Use this registry to render all cassettes.
+    pub fn install(self) {
+        Self::CURRENT.with_borrow_mut(|registry| *registry = Rc::new(self))
+    }
+
+    pub fn current() -> Rc<Self> {
+        Self::CURRENT.with_borrow(|registry| registry.clone())
+    }
+
+    pub fn render(
+        &self,
+        state: &mut crate::cassette::CassetteState,
+        task: &crate::task::CassetteTask,
+    ) -> crate::task::TaskResult<()> {
+        let crate::task::CassetteTask {
+            name,
+            kind,
+            metadata: _,
+            spec,
+        } = task;
+
+        let ctx = crate::cassette::CassetteContext::new(state, task);
+        if !ctx.is_enabled()? {
+            return Ok(ctx.skip());
+        }
+
+        match self.kinds.get(kind).and_then(|kind| kind.render) {
+            Some(render) => render(ctx, spec),
+            None => Err(format!("Unknown type: {name:?} as {kind}")),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TaskKind {
+    name: String,
+    checks: Vec<SpecChecker>,
+    schema: TaskSchemaFn,
+    #[cfg(feature = "ui")]
+    render: Option<TaskRenderFn>,
+}
+
+impl fmt::Debug for TaskKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskKind")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TaskKind {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            checks: Vec::default(),
+            schema: crate::task::TaskSpec::preserve_arbitrary,
+            #[cfg(feature = "ui")]
+            render: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Generate the JSON schema of the task spec.
+    pub fn schema(&self, gen: &mut SchemaGenerator) -> Schema {
+        (self.schema)(gen)
+    }
+
+    /// Check the task specs against the given type, in addition to the previous ones.
+    pub fn with_spec<Spec>(&mut self) -> &mut Self
+    where
+        Spec: DeserializeOwned,
+    {
+        self.checks.push(super::validator::checker::<Spec>());
+        self
+    }
+
+    /// Describe the task specs with the JSON schema of the given type.
+    pub fn with_schema<Spec>(&mut self) -> &mut Self
+    where
+        Spec: JsonSchema,
+    {
+        // NOTE: inlined, as the specs of the task kinds may share the same type name
+        self.schema = |gen| Spec::json_schema(gen);
+        self
+    }
+
+    #[cfg(feature = "ui")]
+    pub fn with_render(&mut self, render: TaskRenderFn) -> &mut Self {
+        self.render = Some(render);
+        self
+    }
+
+    /// Render the tasks with the given state.
+    #[cfg(feature = "ui")]
+    pub fn with_renderer<State, Spec>(&mut self) -> &mut Self
+    where
+        State: super::ComponentRendererExt<Spec>,
+        Spec: DeserializeOwned,
+    {
+        self.with_render(<State as super::ComponentRendererExt<Spec>>::render_with)
+    }
+}
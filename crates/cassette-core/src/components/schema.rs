@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{
+        ArrayValidation, InstanceType, Metadata, RootSchema, Schema, SchemaObject, SingleOrVec,
+        SubschemaValidation,
+    },
+    visit::{visit_schema_object, Visitor},
+    JsonSchema,
+};
+use serde_json::Value;
+
+use crate::task::CassetteTask;
+
+use super::registry::TaskRegistry;
+
+/// Generate the JSON schema of the component specs, discriminated by the task kinds.
+pub(super) fn build(registry: &TaskRegistry) -> RootSchema {
+    let mut gen = SchemaSettings::draft07().into_generator();
+
+    // NOTE: the task fields except the specs are not dynamic
+    let mut task = <CassetteTask>::json_schema(&mut gen).into_object();
+    let fixed: BTreeSet<_> = gen.definitions().keys().cloned().collect();
+
+    let mut kinds = Vec::default();
+    for kind in registry.kinds() {
+        let mut spec = kind.schema(&mut gen);
+        RelaxExpressions.visit_schema(&mut spec);
+        kinds.push((kind.name(), spec));
+    }
+
+    let task_name = <CassetteTask>::schema_name();
+    for (name, schema) in gen.definitions_mut() {
+        if !fixed.contains(name) && *name != task_name {
+            RelaxExpressions.visit_schema(schema);
+        }
+    }
+
+    let properties = &mut task.object().properties;
+    properties.insert(
+        "kind".into(),
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(
+                kinds
+                    .iter()
+                    .map(|(name, _)| Value::String(name.to_string()))
+                    .collect(),
+            ),
+            ..Default::default()
+        }),
+    );
+    if !kinds.is_empty() {
+        task.subschemas().one_of = Some(
+            kinds
+                .into_iter()
+                .map(|(name, spec)| {
+                    let mut branch = SchemaObject::default();
+                    let object = branch.object();
+                    object.properties.insert(
+                        "kind".into(),
+                        Schema::Object(SchemaObject {
+                            const_value: Some(Value::String(name.into())),
+                            ..Default::default()
+                        }),
+                    );
+                    object.properties.insert("spec".into(), spec);
+                    Schema::Object(branch)
+                })
+                .collect(),
+        );
+    }
+
+    // NOTE: the nested tasks, such as `ForEach`, are discriminated too
+    gen.definitions_mut()
+        .insert(task_name, Schema::Object(task));
+
+    let mut schema = SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            title: Some("CassetteComponentSpec".into()),
+            ..Default::default()
+        })),
+        instance_type: Some(InstanceType::Object.into()),
+        ..Default::default()
+    };
+    schema
+        .object()
+        .properties
+        .insert("tasks".into(), gen.subschema_for::<Vec<CassetteTask>>());
+
+    RootSchema {
+        meta_schema: gen.settings().meta_schema.clone(),
+        schema,
+        definitions: gen.take_definitions(),
+    }
+}
+
+/// Describe the task specs in the CRD.
+///
+/// As the CRD schemas should be structural, only the field names are published here.
+pub(super) fn build_crd_tasks(registry: &TaskRegistry, gen: &mut SchemaGenerator) -> Schema {
+    let mut fields: BTreeMap<String, Vec<&str>> = BTreeMap::default();
+    for kind in registry.kinds() {
+        let spec = kind.schema(gen);
+        let spec = gen.dereference(&spec).cloned().unwrap_or(spec);
+        if let Schema::Object(SchemaObject {
+            object: Some(object),
+            ..
+        }) = spec
+        {
+            for name in object.properties.into_keys() {
+                fields.entry(name).or_default().push(kind.name());
+            }
+        }
+    }
+
+    let mut spec = preserve_unknown_fields(None);
+    spec.object().properties = fields
+        .into_iter()
+        .map(|(name, kinds)| {
+            let description = format!("Used by {}", kinds.join(", "));
+            (
+                name,
+                Schema::Object(preserve_unknown_fields(Some(description))),
+            )
+        })
+        .collect();
+
+    let mut task = <CassetteTask>::json_schema(gen).into_object();
+    task.object()
+        .properties
+        .insert("spec".into(), Schema::Object(spec));
+
+    Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::Array.into()),
+        array: Some(Box::new(ArrayValidation {
+            items: Some(Schema::Object(task).into()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    })
+}
+
+fn preserve_unknown_fields(description: Option<String>) -> SchemaObject {
+    let mut schema = SchemaObject {
+        metadata: description.map(|description| {
+            Box::new(Metadata {
+                description: Some(description),
+                ..Default::default()
+            })
+        }),
+        ..Default::default()
+    };
+    schema
+        .extensions
+        .insert("x-kubernetes-preserve-unknown-fields".into(), true.into());
+    schema
+}
+
+/// Accept the dynamic expressions, which are resolved only at runtime.
+struct RelaxExpressions;
+
+impl Visitor for RelaxExpressions {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+
+        if schema.is_ref() {
+            return;
+        }
+
+        if schema.enum_values.is_some() || schema.const_value.is_some() {
+            let mut inner = ::core::mem::take(schema);
+            let metadata = inner.metadata.take();
+            *schema = SchemaObject {
+                metadata,
+                subschemas: Some(Box::new(SubschemaValidation {
+                    any_of: Some(vec![
+                        Schema::Object(inner),
+                        Schema::Object(SchemaObject {
+                            instance_type: Some(InstanceType::String.into()),
+                            ..Default::default()
+                        }),
+                    ]),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            };
+            return;
+        }
+
+        if let Some(instance_type) = &mut schema.instance_type {
+            if !instance_type.contains(&InstanceType::String) {
+                let mut types = match instance_type {
+                    SingleOrVec::Single(ty) => vec![**ty],
+                    SingleOrVec::Vec(tys) => tys.clone(),
+                };
+                types.push(InstanceType::String);
+                *instance_type = types.into();
+            }
+        }
+    }
+}
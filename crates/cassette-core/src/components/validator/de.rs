@@ -0,0 +1,796 @@
+use std::{cell::Cell, collections::BTreeSet, fmt};
+
+use serde::{
+    de::{
+        self,
+        value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+        DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+        VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+use serde_json::Value;
+
+use crate::expr::Template;
+
+/// Try to deserialize a task spec, treating the dynamic expressions as wildcards.
+///
+/// A dynamic field stands for any value of its type, so the other fields are still checked.
+/// If its type cannot take a wildcard, such as a dynamic UUID, the value is skipped
+/// and the spec is checked again without it.
+pub(super) fn check<Spec>(value: &Value) -> Result<(), String>
+where
+    Spec: de::DeserializeOwned,
+{
+    let mut skipped = BTreeSet::default();
+    loop {
+        let pass = Pass {
+            skipped,
+            wildcards: Cell::default(),
+        };
+        let root = LenientValue::new(value, &pass);
+        match root.finish(0, Spec::deserialize(root.clone())) {
+            Ok(_) => return Ok(()),
+            // NOTE: the wildcard stands for a valid value at runtime, so skip it and check the rest
+            Err(Error::Unchecked(path)) => {
+                skipped = pass.skipped;
+                // NOTE: the whole spec depends on the wildcards, so there is nothing left to check
+                if path.is_empty() || !skipped.insert(path) {
+                    return Ok(());
+                }
+            }
+            Err(Error::Invalid { message, .. }) => return Err(message),
+        }
+    }
+}
+
+/// The path of a value in the spec, as the object keys and the array indices.
+type Path = Vec<String>;
+
+#[derive(Debug)]
+enum Error {
+    /// The value depends on a wildcard, so it cannot be checked until runtime.
+    Unchecked(Path),
+    /// The value is invalid, whether by itself or by one of its nested values.
+    Invalid { message: String, nested: bool },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unchecked(_) => f.write_str("Unchecked dynamic expression"),
+            Self::Invalid { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::Invalid {
+            message: msg.to_string(),
+            nested: false,
+        }
+    }
+}
+
+/// The state of a single check of the spec.
+struct Pass {
+    /// The values which depend on the wildcards, found by the previous checks.
+    skipped: BTreeSet<Path>,
+    /// The number of the wildcards taken so far.
+    wildcards: Cell<usize>,
+}
+
+#[derive(Clone)]
+struct LenientValue<'a> {
+    value: &'a Value,
+    path: Path,
+    pass: &'a Pass,
+}
+
+impl<'a> LenientValue<'a> {
+    fn new(value: &'a Value, pass: &'a Pass) -> Self {
+        Self {
+            value,
+            path: Path::default(),
+            pass,
+        }
+    }
+
+    fn nested(&self, key: String, value: &'a Value) -> Self {
+        let mut path = self.path.clone();
+        path.push(key);
+        Self {
+            value,
+            path,
+            pass: self.pass,
+        }
+    }
+
+    fn as_dynamic(&self) -> Result<Option<Template>, Error> {
+        match self.value {
+            Value::String(data) => match data.parse::<Template>() {
+                Ok(template) => Ok(Some(template)),
+                Err(error) => Err(de::Error::custom(format!(
+                    "Failed to parse expression: {error}"
+                ))),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        matches!(self.as_dynamic(), Ok(Some(template)) if !matches!(template, Template::Literal(_)))
+    }
+
+    /// Take a wildcard for this value.
+    fn dynamic(&self) -> Dynamic {
+        self.pass.wildcards.set(self.pass.wildcards.get() + 1);
+        Dynamic::default()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&'a str, Self)> + '_ {
+        let map = self.value.as_object().into_iter().flatten();
+        map.map(|(key, value)| (key.as_str(), self.nested(key.clone(), value)))
+            .filter(|(_, value)| !self.pass.skipped.contains(&value.path))
+    }
+
+    fn items(&self) -> impl Iterator<Item = Self> + '_ {
+        let array = self.value.as_array().into_iter().flatten();
+        array
+            .enumerate()
+            .map(|(index, value)| self.nested(index.to_string(), value))
+            .filter(|value| !self.pass.skipped.contains(&value.path))
+    }
+
+    fn has_skipped(&self) -> bool {
+        self.pass
+            .skipped
+            .iter()
+            .any(|path| path.len() == self.path.len() + 1 && path.starts_with(&self.path))
+    }
+
+    /// Attribute the errors of this value to the wildcards it depends on, if any.
+    ///
+    /// The errors of the nested values are attributed by themselves, so they are kept as they are.
+    /// Note that the errors may be raised after the deserializer returns, as of the untagged enums,
+    /// so they should be attributed by the caller.
+    fn finish<T>(&self, wildcards: usize, result: Result<T, Error>) -> Result<T, Error> {
+        match result {
+            Err(Error::Invalid {
+                message,
+                nested: false,
+            }) => {
+                if self.pass.wildcards.get() > wildcards || self.has_skipped() || self.is_dynamic()
+                {
+                    Err(Error::Unchecked(self.path.clone()))
+                } else {
+                    Err(Error::Invalid {
+                        message,
+                        nested: true,
+                    })
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+/// The entries of an object, attributing the errors of each value.
+struct LenientMap<'a, I> {
+    entries: I,
+    value: Option<LenientValue<'a>>,
+}
+
+impl<'a, 'de, I> MapAccess<'de> for LenientMap<'a, I>
+where
+    I: Iterator<Item = (&'a str, LenientValue<'a>)>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(IntoDeserializer::<Error>::into_deserializer(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => {
+                let wildcards = value.pass.wildcards.get();
+                value.finish(wildcards, seed.deserialize(value.clone()))
+            }
+            None => Err(de::Error::custom("Value is missing")),
+        }
+    }
+}
+
+/// The items of an array, attributing the errors of each item.
+struct LenientSeq<I>(I);
+
+impl<'a, 'de, I> SeqAccess<'de> for LenientSeq<I>
+where
+    I: Iterator<Item = LenientValue<'a>>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.0.next() {
+            Some(value) => {
+                let wildcards = value.pass.wildcards.get();
+                value
+                    .finish(wildcards, seed.deserialize(value.clone()))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+macro_rules! deserialize_lenient {
+    ( $( $method:ident ( $( $arg:ident : $ty:ty ),* ), )* ) => {
+        $(
+            fn $method<V>(self, $( $arg: $ty, )* visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.as_dynamic()? {
+                    Some(Template::Literal(_)) | None => self.deserialize_any(visitor),
+                    Some(_) => self.dynamic().$method($( $arg, )* visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for LenientValue<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(data) => visitor.visit_bool(*data),
+            Value::Number(data) => match (data.as_u64(), data.as_i64(), data.as_f64()) {
+                (Some(data), _, _) => visitor.visit_u64(data),
+                (None, Some(data), _) => visitor.visit_i64(data),
+                (None, None, data) => visitor.visit_f64(data.unwrap_or_default()),
+            },
+            Value::String(data) => match self.as_dynamic()? {
+                Some(Template::Literal(text)) => visitor.visit_string(text),
+                // NOTE: the type is unknown here, as of the untagged enums and the flattened fields,
+                //       so the expression is kept and its containers are attributed to the wildcard
+                _ => {
+                    self.dynamic();
+                    visitor.visit_str(data)
+                }
+            },
+            Value::Array(_) => visitor.visit_seq(LenientSeq(self.items())),
+            Value::Object(_) => visitor.visit_map(LenientMap {
+                entries: self.entries(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match (self.value, self.as_dynamic()?) {
+            (Value::String(_), Some(Template::Literal(text))) => {
+                visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(text))
+            }
+            (Value::String(_), Some(_)) => self.dynamic().deserialize_enum(name, variants, visitor),
+            (Value::Object(_), _) => visitor.visit_enum(MapAccessDeserializer::new(LenientMap {
+                entries: self.entries(),
+                value: None,
+            })),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    deserialize_lenient! {
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+    }
+
+    forward_to_deserialize_any! {
+        identifier
+    }
+}
+
+/// A wildcard for a dynamic expression, which is resolved only at runtime.
+#[derive(Copy, Clone, Default)]
+struct Dynamic {
+    depth: usize,
+}
+
+impl Dynamic {
+    /// The maximum depth of the nested wildcards, as the recursive types may never end.
+    const MAX_DEPTH: usize = 32;
+
+    fn nested(self) -> Result<Self, Error> {
+        if self.depth < Self::MAX_DEPTH {
+            Ok(Self {
+                depth: self.depth + 1,
+            })
+        } else {
+            Err(de::Error::custom("Too deeply nested dynamic expression"))
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Dynamic {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Dynamic {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(Default::default())
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i64(Default::default())
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u64(Default::default())
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_f64(Default::default())
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_char(Default::default())
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(Default::default())
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bytes(Default::default())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(SeqDeserializer::new(::core::iter::empty::<Self>()))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let item = self.nested()?;
+        visitor.visit_seq(SeqDeserializer::new((0..len).map(|_| item)))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(MapDeserializer::new(::core::iter::empty::<(&str, Self)>()))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // NOTE: every field of the struct is a wildcard as well
+        let item = self.nested()?;
+        visitor.visit_map(MapDeserializer::new(
+            fields.iter().map(|field| (*field, item)),
+        ))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // NOTE: any variant is fine, so take the first one
+        match variants.first() {
+            Some(&variant) => visitor.visit_enum(DynamicVariant {
+                name: variant,
+                value: self.nested()?,
+            }),
+            None => Err(de::Error::custom("Empty enum cannot be dynamic")),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+}
+
+/// A wildcard for a dynamic enum, which takes the given variant.
+struct DynamicVariant {
+    name: &'static str,
+    value: Dynamic,
+}
+
+impl<'de> EnumAccess<'de> for DynamicVariant {
+    type Error = Error;
+    type Variant = Dynamic;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let name = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(self.name))?;
+        Ok((name, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Dynamic {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_struct("", fields, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::check;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
+    struct Spec {
+        #[serde(default)]
+        id: Option<NonZeroU32>,
+        #[serde(default)]
+        source: Option<Source>,
+        title: String,
+        total: u32,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    #[allow(dead_code)]
+    enum Source {
+        Inline { data: Vec<u32> },
+        Remote { url: String },
+    }
+
+    #[test]
+    fn valid_spec() {
+        assert_eq!(
+            check::<Spec>(&json!({"title": "Hello", "total": 3})),
+            Ok(())
+        );
+        assert_eq!(
+            check::<Spec>(
+                &json!({"id": 1, "source": {"data": [1, 2]}, "title": "Hello", "total": 3})
+            ),
+            Ok(()),
+        );
+        assert_eq!(
+            check::<Spec>(&json!({"title": "${ :/name/text }", "total": ":/count"})),
+            Ok(()),
+        );
+    }
+
+    #[test]
+    fn wrong_type() {
+        let error = check::<Spec>(&json!({"title": "Hello", "total": "three"})).unwrap_err();
+        assert!(error.contains("invalid type"), "{error}");
+    }
+
+    #[test]
+    fn missing_field() {
+        let error = check::<Spec>(&json!({"title": "Hello"})).unwrap_err();
+        assert!(error.contains("missing field `total`"), "{error}");
+    }
+
+    #[test]
+    fn unchecked_wildcard() {
+        // NOTE: a dynamic `NonZeroU32` cannot take the default value
+        assert_eq!(
+            check::<Spec>(&json!({"id": "${ :/id }", "title": "Hello", "total": 3})),
+            Ok(()),
+        );
+
+        // NOTE: the rest of the spec is still checked
+        let error = check::<Spec>(&json!({"id": "${ :/id }", "title": "Hello", "total": "three"}))
+            .unwrap_err();
+        assert!(error.contains("invalid type"), "{error}");
+    }
+
+    #[test]
+    fn wildcard_in_untagged_enum() {
+        assert_eq!(
+            check::<Spec>(
+                &json!({"source": {"url": "${ :/url/text }"}, "title": "Hello", "total": 3})
+            ),
+            Ok(()),
+        );
+        assert_eq!(
+            check::<Spec>(&json!({"source": {"data": "~/items"}, "title": "Hello", "total": 3})),
+            Ok(()),
+        );
+
+        // NOTE: the rest of the spec is still checked
+        let error = check::<Spec>(
+            &json!({"source": {"data": "~/items"}, "title": "Hello", "total": "three"}),
+        )
+        .unwrap_err();
+        assert!(error.contains("invalid type"), "{error}");
+
+        // NOTE: the untagged enums without any wildcards are checked as usual
+        let error =
+            check::<Spec>(&json!({"source": {"data": "items"}, "title": "Hello", "total": 3}))
+                .unwrap_err();
+        assert!(error.contains("did not match any variant"), "{error}");
+    }
+}
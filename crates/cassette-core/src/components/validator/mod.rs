@@ -0,0 +1,240 @@
+mod de;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    expr::{Expr, ExprScope, Template},
+    task::CassetteTask,
+};
+
+use super::CassetteComponentSpec;
+
+pub(super) type SpecChecker = fn(&Value) -> Result<(), String>;
+
+pub(super) fn checker<Spec>() -> SpecChecker
+where
+    Spec: DeserializeOwned,
+{
+    self::de::check::<Spec>
+}
+
+/// A static checker of the cassette task pipelines.
+#[derive(Clone, Debug, Default)]
+pub struct CassetteValidator {
+    kinds: BTreeMap<String, Vec<SpecChecker>>,
+}
+
+impl CassetteValidator {
+    /// Register a task kind, whose spec is checked against the given type.
+    pub fn insert<Spec>(&mut self, kind: impl Into<String>)
+    where
+        Spec: DeserializeOwned,
+    {
+        self.insert_checker(kind, vec![checker::<Spec>()]);
+    }
+
+    /// Register a task kind, whose spec is not checked.
+    pub fn insert_any(&mut self, kind: impl Into<String>) {
+        self.insert_checker(kind, Vec::default());
+    }
+
+    pub(super) fn insert_checker(&mut self, kind: impl Into<String>, checkers: Vec<SpecChecker>) {
+        self.kinds.insert(kind.into(), checkers);
+    }
+
+    pub fn validate(&self, spec: &CassetteComponentSpec) -> Vec<CassetteDiagnostic> {
+        let mut diagnostics = Vec::default();
+        self.validate_tasks(
+            &mut diagnostics,
+            &BTreeSet::default(),
+            &spec.tasks,
+            None,
+            "/tasks",
+        );
+        diagnostics
+    }
+
+    fn validate_tasks<'a>(
+        &self,
+        diagnostics: &mut Vec<CassetteDiagnostic>,
+        outer: &BTreeSet<&'a str>,
+        tasks: &'a [CassetteTask],
+        index: Option<usize>,
+        path: &str,
+    ) {
+        let names: BTreeSet<_> = tasks.iter().map(|task| task.name.as_str()).collect();
+        let mut seen = BTreeSet::default();
+        let mut visible = outer.clone();
+
+        for (task_index, task) in tasks.iter().enumerate() {
+            let index = index.unwrap_or(task_index);
+            let path = format!("{path}/{task_index}");
+            let mut report = |kind, path: String, message: String| {
+                diagnostics.push(CassetteDiagnostic {
+                    index,
+                    task: task.name.clone(),
+                    path,
+                    kind,
+                    message,
+                })
+            };
+
+            // duplicated names
+            if !seen.insert(task.name.as_str()) {
+                report(
+                    CassetteDiagnosticKind::DuplicatedName,
+                    format!("{path}/name"),
+                    format!("Duplicated task name: {:?}", task.name),
+                );
+            }
+
+            // unknown kinds
+            match self.kinds.get(&task.kind) {
+                Some(checkers) => {
+                    if let Some(error) = checkers.iter().find_map(|check| check(&task.spec.0).err())
+                    {
+                        report(
+                            CassetteDiagnosticKind::InvalidSpec,
+                            format!("{path}/spec"),
+                            format!("Failed to parse task spec: {error}"),
+                        )
+                    }
+                }
+                None => report(
+                    CassetteDiagnosticKind::UnknownKind,
+                    format!("{path}/kind"),
+                    format!("Unknown type: {:?} as {}", task.name, task.kind),
+                ),
+            }
+
+            // references
+            let mut references = Vec::default();
+            if let Some(when) = task.metadata.when.as_deref() {
+                let path = format!("{path}/metadata/when");
+                match when.parse::<Expr>() {
+                    Ok(expr) => references.extend(
+                        expr.references()
+                            .into_iter()
+                            .map(|(scope, key)| (path.clone(), scope, key.to_string())),
+                    ),
+                    Err(error) => report(
+                        CassetteDiagnosticKind::InvalidExpression,
+                        path,
+                        format!("Failed to parse expression: {error}"),
+                    ),
+                }
+            }
+            match &task.spec.0 {
+                Value::Object(map) => map
+                    .iter()
+                    // NOTE: nested tasks are validated in their own scope
+                    .filter(|(key, _)| key.as_str() != "tasks")
+                    .for_each(|(key, value)| {
+                        let path = format!("{path}/spec/{}", escape_pointer(key));
+                        collect_references(&mut references, &mut report, value, &path)
+                    }),
+                value => {
+                    collect_references(&mut references, &mut report, value, &format!("{path}/spec"))
+                }
+            }
+
+            for (path, scope, key) in references {
+                if scope != ExprScope::Data {
+                    continue;
+                }
+                let name = key
+                    .trim_start_matches('/')
+                    .split('/')
+                    .next()
+                    .unwrap_or_default();
+                if name == task.name || visible.contains(name) {
+                    continue;
+                }
+
+                let message = if names.contains(name) {
+                    format!("Reference to a later task: {key:?}")
+                } else {
+                    format!("Reference to an unknown task: {key:?}")
+                };
+                report(CassetteDiagnosticKind::InvalidReference, path, message)
+            }
+
+            // nested tasks, such as `ForEach`
+            visible.insert(task.name.as_str());
+            if let Some(nested) = task.spec.0.get("tasks") {
+                if let Ok(nested) = ::serde_json::from_value::<Vec<CassetteTask>>(nested.clone()) {
+                    self.validate_tasks(
+                        diagnostics,
+                        &visible,
+                        &nested,
+                        Some(index),
+                        &format!("{path}/spec/tasks"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn collect_references(
+    references: &mut Vec<(String, ExprScope, String)>,
+    report: &mut impl FnMut(CassetteDiagnosticKind, String, String),
+    value: &Value,
+    path: &str,
+) {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => (),
+        Value::String(data) => match data.parse::<Template>() {
+            Ok(template) => references.extend(
+                template
+                    .references()
+                    .into_iter()
+                    .map(|(scope, key)| (path.into(), scope, key.to_string())),
+            ),
+            Err(error) => report(
+                CassetteDiagnosticKind::InvalidExpression,
+                path.into(),
+                format!("Failed to parse expression: {error}"),
+            ),
+        },
+        Value::Array(array) => array.iter().enumerate().for_each(|(index, value)| {
+            collect_references(references, report, value, &format!("{path}/{index}"))
+        }),
+        Value::Object(map) => map.iter().for_each(|(key, value)| {
+            let path = format!("{path}/{}", escape_pointer(key));
+            collect_references(references, report, value, &path)
+        }),
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CassetteDiagnostic {
+    /// The index of the (outermost) task
+    pub index: usize,
+    /// The name of the task
+    pub task: String,
+    /// The JSON pointer of the invalid field, from the component spec
+    pub path: String,
+    pub kind: CassetteDiagnosticKind,
+    pub message: String,
+}
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+pub enum CassetteDiagnosticKind {
+    DuplicatedName,
+    InvalidExpression,
+    InvalidReference,
+    InvalidSpec,
+    UnknownKind,
+}
@@ -0,0 +1,14 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::SchemaType;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSpec {
+    /// The schema of each item, whose paths are relative to the item
+    pub items: Box<SchemaType>,
+    #[serde(default)]
+    pub default: Option<Vec<Value>>,
+}
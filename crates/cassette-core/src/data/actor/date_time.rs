@@ -0,0 +1,66 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSpec {
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub format: SchemaDateTimeFormat,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl SchemaSpec {
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        let value = match value {
+            Value::Null => return super::validate_required(self.required),
+            Value::String(value) if value.is_empty() => {
+                return super::validate_required(self.required)
+            }
+            Value::String(value) => value,
+            _ => return Err(format!("Should be a {}", self.format.name())),
+        };
+
+        // NOTE: the seconds are optional, as the browsers omit them by default
+        let is_valid = match self.format {
+            SchemaDateTimeFormat::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            SchemaDateTimeFormat::Time => ["%H:%M", "%H:%M:%S"]
+                .into_iter()
+                .any(|format| NaiveTime::parse_from_str(value, format).is_ok()),
+            SchemaDateTimeFormat::DateTime => ["%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"]
+                .into_iter()
+                .any(|format| NaiveDateTime::parse_from_str(value, format).is_ok()),
+        };
+        if is_valid {
+            Ok(())
+        } else {
+            Err(format!("Should be a {}", self.format.name()))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaDateTimeFormat {
+    /// `YYYY-MM-DD`
+    Date,
+    /// `hh:mm`
+    Time,
+    /// `YYYY-MM-DDThh:mm`, without any timezones
+    #[default]
+    DateTime,
+}
+
+impl SchemaDateTimeFormat {
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Time => "time",
+            Self::DateTime => "date and time",
+        }
+    }
+}
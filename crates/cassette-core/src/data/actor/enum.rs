@@ -1,9 +1,24 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SchemaSpec {
     #[serde(default)]
     pub choices: Vec<String>,
     #[serde(default)]
     pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl SchemaSpec {
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        match value {
+            Value::Null => super::validate_required(self.required),
+            Value::String(value) if value.is_empty() => super::validate_required(self.required),
+            Value::String(value) if self.choices.contains(value) => Ok(()),
+            _ => Err(format!("Should be one of: {}", self.choices.join(", "))),
+        }
+    }
 }
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSpec {
+    /// The accepted file types, such as `.pem,.crt`
+    #[serde(default)]
+    pub accept: Option<String>,
+    #[serde(default)]
+    pub encoding: SchemaFileEncoding,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl SchemaSpec {
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        match value {
+            Value::String(_) => Ok(()),
+            _ => super::validate_required(self.required),
+        }
+    }
+}
+
+/// How to embed the uploaded file into a string.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaFileEncoding {
+    /// As it is, which should be valid UTF-8
+    #[default]
+    Text,
+    /// As a standard base64 string
+    Base64,
+}
@@ -0,0 +1,25 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A free-form object, edited as a JSON text.
+///
+/// It is used for the maps whose keys are unknown, such as the labels.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSpec {
+    #[serde(default)]
+    pub default: Option<Map<String, Value>>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl SchemaSpec {
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        match value {
+            Value::Null => super::validate_required(self.required),
+            Value::Object(_) => Ok(()),
+            _ => Err("Should be a JSON object".into()),
+        }
+    }
+}
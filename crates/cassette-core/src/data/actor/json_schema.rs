@@ -0,0 +1,253 @@
+use serde_json::{Number, Value};
+
+use super::{Schema, SchemaArray, SchemaPath, SchemaType};
+
+impl SchemaArray {
+    /// Convert the properties of a JSON schema, filling the defaults from the given values.
+    ///
+    /// The schemas include the `values.schema.json` of the helm charts
+    /// and the `openAPIV3Schema` of the kubernetes CRDs.
+    pub fn from_json_schema(schema: &Value, values: &Value) -> Self {
+        build_fields(schema, values)
+    }
+
+    /// Guess the fields from the given values, such as the `values.yaml` of the helm charts.
+    pub fn from_values(values: &Value) -> Self {
+        guess_fields(values)
+    }
+}
+
+fn build_fields(schema: &Value, values: &Value) -> SchemaArray {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return SchemaArray::default();
+    };
+    let required: Vec<_> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|keys| keys.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    SchemaArray(
+        properties
+            .iter()
+            .filter_map(|(key, property)| {
+                let default = values.get(key).unwrap_or(&Value::Null);
+                let ty = build_type(property, default, required.contains(&key.as_str()))?;
+                let name = property.get("title").and_then(Value::as_str).unwrap_or(key);
+                Some(Schema::new(name, SchemaPath::from_key(key), ty))
+            })
+            .collect(),
+    )
+}
+
+fn build_type(schema: &Value, default: &Value, required: bool) -> Option<SchemaType> {
+    // NOTE: the given values take precedence over the schema defaults
+    let default = match default {
+        Value::Null => schema.get("default").unwrap_or(&Value::Null),
+        default => default,
+    };
+    // NOTE: the fields with defaults are optional, as the backends fall back to them
+    let required = required && default.is_null();
+
+    // NOTE: kubernetes quantities, such as `100m` or `1`
+    if schema.get("x-kubernetes-int-or-string") == Some(&Value::Bool(true)) {
+        return Some(SchemaType::String(super::string::SchemaSpec {
+            default: match default {
+                Value::Number(value) => Some(value.to_string()),
+                value => value.as_str().map(Into::into),
+            },
+            required,
+            ..Default::default()
+        }));
+    }
+
+    let ty = match schema.get("type") {
+        Some(Value::String(ty)) => ty.as_str(),
+        // NOTE: nullable fields, such as `["string", "null"]`
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|ty| *ty != "null")?,
+        _ if schema.get("properties").is_some() || is_free_form(schema) => "object",
+        _ => return guess_type(default),
+    };
+
+    if let Some(choices) = string_choices(schema) {
+        return Some(SchemaType::Enum(super::r#enum::SchemaSpec {
+            choices,
+            default: default.as_str().map(Into::into),
+            required,
+        }));
+    }
+
+    match ty {
+        "boolean" => Some(SchemaType::Boolean(super::boolean::SchemaSpec {
+            default: default.as_bool(),
+        })),
+        "integer" | "number" => Some(SchemaType::Number(super::number::SchemaSpec {
+            default: as_number(default),
+            required,
+            min: schema.get("minimum").and_then(Value::as_f64),
+            max: schema.get("maximum").and_then(Value::as_f64),
+            step: schema
+                .get("multipleOf")
+                .and_then(Value::as_f64)
+                .or((ty == "integer").then_some(1.0)),
+        })),
+        "string" => match schema.get("format").and_then(Value::as_str) {
+            Some("date") => Some(SchemaType::DateTime(super::date_time::SchemaSpec {
+                default: default.as_str().map(Into::into),
+                format: super::date_time::SchemaDateTimeFormat::Date,
+                required,
+            })),
+            // NOTE: the defaults are dropped, as they may have timezones
+            Some("date-time") => Some(SchemaType::DateTime(super::date_time::SchemaSpec {
+                default: None,
+                format: super::date_time::SchemaDateTimeFormat::DateTime,
+                required,
+            })),
+            Some("password") => Some(SchemaType::Secret(super::secret::SchemaSpec {
+                required,
+                min_length: as_usize(schema.get("minLength")),
+            })),
+            _ => Some(SchemaType::String(super::string::SchemaSpec {
+                default: default.as_str().map(Into::into),
+                required,
+                min_length: as_usize(schema.get("minLength")),
+                max_length: as_usize(schema.get("maxLength")),
+                // NOTE: the patterns are checked by the server anyway,
+                //       so the ones the regex engine cannot compile (e.g. lookarounds) are skipped
+                pattern: schema
+                    .get("pattern")
+                    .and_then(Value::as_str)
+                    .and_then(|pattern| pattern.parse().ok()),
+                message: None,
+                format: None,
+            })),
+        },
+        "array" => {
+            let items = schema.get("items").unwrap_or(&Value::Null);
+            match string_choices(items) {
+                Some(choices) => Some(SchemaType::MultiEnum(super::multi_enum::SchemaSpec {
+                    choices,
+                    default: default
+                        .as_array()
+                        .map(|values| {
+                            values
+                                .iter()
+                                .filter_map(Value::as_str)
+                                .map(Into::into)
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                })),
+                None => {
+                    let sample = default
+                        .as_array()
+                        .and_then(|values| values.first())
+                        .unwrap_or(&Value::Null);
+                    Some(SchemaType::Array(super::array::SchemaSpec {
+                        items: Box::new(build_type(items, sample, false)?),
+                        default: default.as_array().cloned(),
+                    }))
+                }
+            }
+        }
+        "object" => {
+            let fields = build_fields(schema, default);
+            if !fields.0.is_empty() {
+                Some(SchemaType::Object(super::object::SchemaSpec { fields }))
+            } else if is_free_form(schema) {
+                // NOTE: the maps without any known properties are edited as they are
+                Some(SchemaType::Json(super::json::SchemaSpec {
+                    default: default.as_object().cloned(),
+                    required,
+                }))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn guess_fields(values: &Value) -> SchemaArray {
+    let Value::Object(values) = values else {
+        return SchemaArray::default();
+    };
+
+    SchemaArray(
+        values
+            .iter()
+            .filter_map(|(key, value)| {
+                Some(Schema::new(
+                    key.clone(),
+                    SchemaPath::from_key(key),
+                    guess_type(value)?,
+                ))
+            })
+            .collect(),
+    )
+}
+
+fn guess_type(value: &Value) -> Option<SchemaType> {
+    match value {
+        Value::Null => None,
+        Value::Bool(value) => Some(SchemaType::Boolean(super::boolean::SchemaSpec {
+            default: Some(*value),
+        })),
+        Value::Number(value) => Some(SchemaType::Number(super::number::SchemaSpec {
+            default: Some(value.clone()),
+            ..Default::default()
+        })),
+        Value::String(value) => Some(SchemaType::String(super::string::SchemaSpec {
+            default: Some(value.clone()),
+            ..Default::default()
+        })),
+        // NOTE: the items are guessed from the first one, so the empty arrays are skipped
+        Value::Array(values) => Some(SchemaType::Array(super::array::SchemaSpec {
+            items: Box::new(guess_type(values.first()?)?),
+            default: Some(values.clone()),
+        })),
+        Value::Object(values) => {
+            let fields = guess_fields(value);
+            if fields.0.is_empty() {
+                // NOTE: the empty maps are filled by the users, such as the annotations
+                Some(SchemaType::Json(super::json::SchemaSpec {
+                    default: Some(values.clone()),
+                    required: false,
+                }))
+            } else {
+                Some(SchemaType::Object(super::object::SchemaSpec { fields }))
+            }
+        }
+    }
+}
+
+/// Whether the object accepts any other keys, such as the labels or the helm values.
+fn is_free_form(schema: &Value) -> bool {
+    schema.get("x-kubernetes-preserve-unknown-fields") == Some(&Value::Bool(true))
+        || schema
+            .get("additionalProperties")
+            .is_some_and(|properties| *properties != Value::Bool(false))
+}
+
+fn string_choices(schema: &Value) -> Option<Vec<String>> {
+    schema
+        .get("enum")?
+        .as_array()?
+        .iter()
+        .map(|choice| choice.as_str().map(Into::into))
+        .collect()
+}
+
+fn as_number(value: &Value) -> Option<Number> {
+    match value {
+        Value::Number(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn as_usize(value: Option<&Value>) -> Option<usize> {
+    value?.as_u64()?.try_into().ok()
+}
@@ -1,15 +1,24 @@
+pub mod array;
 pub mod boolean;
+pub mod date_time;
 pub mod r#enum;
+pub mod file;
+pub mod json;
+mod json_schema;
+pub mod multi_enum;
 pub mod number;
+pub mod object;
+pub mod secret;
 pub mod string;
 
-use std::{fmt, str::FromStr};
+use std::{collections::BTreeMap, fmt, str::FromStr};
 
+use schemars::JsonSchema;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Value};
 use thiserror::Error;
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SchemaActor {
     #[serde(default)]
@@ -19,26 +28,204 @@ pub struct SchemaActor {
     pub update: Option<SchemaArray>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
 pub struct SchemaArray(pub Vec<Schema>);
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+impl SchemaArray {
+    /// Validate the data, collecting the error messages by the paths of the invalid fields.
+    ///
+    /// The hidden fields, including their children, are not validated.
+    pub fn validate(
+        &self,
+        data: &Value,
+        is_visible: &dyn Fn(&SchemaPath) -> bool,
+    ) -> BTreeMap<String, String> {
+        let mut errors = BTreeMap::default();
+        self.validate_at(&SchemaPath::default(), data, is_visible, &mut errors);
+        errors
+    }
+
+    fn validate_at(
+        &self,
+        path: &SchemaPath,
+        data: &Value,
+        is_visible: &dyn Fn(&SchemaPath) -> bool,
+        errors: &mut BTreeMap<String, String>,
+    ) {
+        for field in &self.0 {
+            let path = path.join(&field.path);
+            if is_visible(&path) {
+                field.ty.validate_at(&path, data, is_visible, errors)
+            }
+        }
+    }
+
+    /// Collect all fields with their absolute paths, including the nested ones.
+    ///
+    /// The parents come before their children.
+    pub fn fields(&self, data: &Value) -> Vec<(SchemaPath, &Schema)> {
+        let mut fields = Vec::default();
+        self.collect_fields(&SchemaPath::default(), data, &mut fields);
+        fields
+    }
+
+    fn collect_fields<'a>(
+        &'a self,
+        path: &SchemaPath,
+        data: &Value,
+        fields: &mut Vec<(SchemaPath, &'a Schema)>,
+    ) {
+        for field in &self.0 {
+            let path = path.join(&field.path);
+            fields.push((path.clone(), field));
+            field.ty.collect_fields(&path, data, fields);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Schema {
     pub name: String,
     pub path: SchemaPath,
     #[serde(flatten)]
     pub ty: SchemaType,
+
+    /// Show the field only if the expression is truthy, such as `~/form/gpu/enabled`.
+    #[serde(default)]
+    pub visible_if: Option<String>,
+
+    /// Let the field be edited only if the expression is truthy.
+    #[serde(default)]
+    pub enabled_if: Option<String>,
+
+    /// Fill the empty field with the template, such as `~/user/namespace`.
+    #[serde(default)]
+    pub computed_default: Option<String>,
+}
+
+impl Schema {
+    pub fn new(name: impl Into<String>, path: SchemaPath, ty: SchemaType) -> Self {
+        Self {
+            name: name.into(),
+            path,
+            ty,
+            visible_if: None,
+            enabled_if: None,
+            computed_default: None,
+        }
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum SchemaType {
     Boolean(self::boolean::SchemaSpec),
     Number(self::number::SchemaSpec),
     String(self::string::SchemaSpec),
     Enum(self::r#enum::SchemaSpec),
+    Array(self::array::SchemaSpec),
+    Object(self::object::SchemaSpec),
+    DateTime(self::date_time::SchemaSpec),
+    Secret(self::secret::SchemaSpec),
+    MultiEnum(self::multi_enum::SchemaSpec),
+    File(self::file::SchemaSpec),
+    Json(self::json::SchemaSpec),
+}
+
+impl SchemaType {
+    fn validate_at(
+        &self,
+        path: &SchemaPath,
+        data: &Value,
+        is_visible: &dyn Fn(&SchemaPath) -> bool,
+        errors: &mut BTreeMap<String, String>,
+    ) {
+        let value = path.get(data);
+        let result = match self {
+            Self::Boolean(_) | Self::MultiEnum(_) => Ok(()),
+            Self::Number(spec) => spec.validate(value),
+            Self::String(spec) => spec.validate(value),
+            Self::Enum(spec) => spec.validate(value),
+            Self::Array(spec) => {
+                if let Value::Array(items) = value {
+                    for index in 0..items.len() {
+                        let path = path.push(SchemaPathItem::List(index));
+                        spec.items.validate_at(&path, data, is_visible, errors);
+                    }
+                }
+                Ok(())
+            }
+            Self::Object(spec) => {
+                spec.fields.validate_at(path, data, is_visible, errors);
+                Ok(())
+            }
+            Self::DateTime(spec) => spec.validate(value),
+            Self::Secret(spec) => spec.validate(value),
+            Self::File(spec) => spec.validate(value),
+            Self::Json(spec) => spec.validate(value),
+        };
+        if let Err(error) = result {
+            errors.insert(path.to_string(), error);
+        }
+    }
+
+    fn collect_fields<'a>(
+        &'a self,
+        path: &SchemaPath,
+        data: &Value,
+        fields: &mut Vec<(SchemaPath, &'a Schema)>,
+    ) {
+        match self {
+            Self::Array(spec) => {
+                if let Value::Array(items) = path.get(data) {
+                    for index in 0..items.len() {
+                        let path = path.push(SchemaPathItem::List(index));
+                        spec.items.collect_fields(&path, data, fields);
+                    }
+                }
+            }
+            Self::Object(spec) => spec.fields.collect_fields(path, data, fields),
+            _ => (),
+        }
+    }
+
+    /// The initial value of a new field, such as the new items of the arrays.
+    pub fn default_value(&self) -> Value {
+        match self {
+            Self::Boolean(spec) => Value::Bool(spec.default.unwrap_or_default()),
+            Self::Number(spec) => spec.default.clone().map_or(Value::Null, Value::Number),
+            Self::String(spec) => spec.default.clone().map_or(Value::Null, Value::String),
+            Self::Enum(spec) => spec.default.clone().map_or(Value::Null, Value::String),
+            Self::Array(spec) => Value::Array(spec.default.clone().unwrap_or_default()),
+            Self::Object(spec) => {
+                let mut value = Value::Object(Map::default());
+                for field in &spec.fields.0 {
+                    let default = field.ty.default_value();
+                    if !default.is_null() {
+                        field.path.set(&mut value, default);
+                    }
+                }
+                value
+            }
+            Self::DateTime(spec) => spec.default.clone().map_or(Value::Null, Value::String),
+            // NOTE: the secrets and the files have no defaults, as they should be given by the users
+            Self::Secret(_) | Self::File(_) => Value::Null,
+            Self::MultiEnum(spec) => {
+                Value::Array(spec.default.iter().cloned().map(Value::String).collect())
+            }
+            Self::Json(spec) => spec.default.clone().map_or(Value::Null, Value::Object),
+        }
+    }
+}
+
+fn validate_required(required: bool) -> Result<(), String> {
+    if required {
+        Err("This field is required".into())
+    } else {
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -92,6 +279,17 @@ impl Serialize for SchemaPath {
     }
 }
 
+impl JsonSchema for SchemaPath {
+    fn schema_name() -> String {
+        "SchemaPath".into()
+    }
+
+    fn json_schema(gen: &mut ::schemars::gen::SchemaGenerator) -> ::schemars::schema::Schema {
+        // NOTE: serialized as a json pointer
+        String::json_schema(gen)
+    }
+}
+
 impl<'de> Deserialize<'de> for SchemaPath {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -120,6 +318,23 @@ impl<'de> Deserialize<'de> for SchemaPath {
 }
 
 impl SchemaPath {
+    /// A path of a single object key, which needs no escapes.
+    pub fn from_key(key: impl Into<String>) -> Self {
+        Self(vec![SchemaPathItem::Object(key.into())])
+    }
+
+    /// Append the relative path, such as the fields of the nested objects.
+    pub fn join(&self, child: &Self) -> Self {
+        Self(self.0.iter().chain(&child.0).cloned().collect())
+    }
+
+    /// Append an item, such as the index of an array item.
+    pub fn push(&self, item: SchemaPathItem) -> Self {
+        let mut path = self.clone();
+        path.0.push(item);
+        path
+    }
+
     pub fn get<'a>(&self, target: &'a Value) -> &'a Value {
         let mut target = target;
         for item in &self.0 {
@@ -181,6 +396,31 @@ impl SchemaPath {
     pub fn set(&self, target: &mut Value, value: Value) {
         *self.get_mut(target) = value
     }
+
+    /// Remove the value, keeping the other ones as they are.
+    pub fn remove(&self, target: &mut Value) -> Option<Value> {
+        let (last, parents) = self.0.split_last()?;
+        let mut target = target;
+        for item in parents {
+            target = match (item, target) {
+                (&SchemaPathItem::List(i), Value::Array(children)) => children.get_mut(i)?,
+                (&SchemaPathItem::List(i), Value::Object(children)) => {
+                    children.get_mut(&i.to_string())?
+                }
+                (SchemaPathItem::Object(i), Value::Object(children)) => children.get_mut(i)?,
+                _ => return None,
+            };
+        }
+        match (last, target) {
+            // NOTE: the array items are nulled rather than removed to keep the others in place
+            (&SchemaPathItem::List(i), Value::Array(children)) => children
+                .get_mut(i)
+                .map(|child| ::core::mem::replace(child, Value::Null)),
+            (&SchemaPathItem::List(i), Value::Object(children)) => children.remove(&i.to_string()),
+            (SchemaPathItem::Object(i), Value::Object(children)) => children.remove(i),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Error)]
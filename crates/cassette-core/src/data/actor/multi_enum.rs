@@ -0,0 +1,10 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SchemaSpec {
+    #[serde(default)]
+    pub choices: Vec<String>,
+    #[serde(default)]
+    pub default: Vec<String>,
+}
@@ -1,9 +1,104 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::Number;
+use serde_json::{Number, Value};
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SchemaSpec {
     #[serde(default)]
     pub default: Option<Number>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// The interval of the valid numbers, starting from `min` or zero
+    #[serde(default)]
+    pub step: Option<f64>,
+}
+
+impl SchemaSpec {
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        let value = match value {
+            Value::Null => return super::validate_required(self.required),
+            Value::Number(value) => value.as_f64(),
+            Value::String(value) if value.trim().is_empty() => {
+                return super::validate_required(self.required)
+            }
+            Value::String(value) => value.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| "Should be a number".to_string())?;
+
+        if let Some(min) = self.min.filter(|&min| value < min) {
+            return Err(format!("Should be at least {min}"));
+        }
+        if let Some(max) = self.max.filter(|&max| value > max) {
+            return Err(format!("Should be at most {max}"));
+        }
+        if let Some(step) = self.step.filter(|&step| step > 0.0) {
+            let steps = (value - self.min.unwrap_or_default()) / step;
+            if (steps - steps.round()).abs() > 1e-9 {
+                return Err(format!("Should be in steps of {step}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn min_max() {
+        let spec = SchemaSpec {
+            min: Some(1.0),
+            max: Some(10.0),
+            ..Default::default()
+        };
+        for value in [json!(1), json!(10), json!(5.5), json!(" 3 ")] {
+            assert_eq!(spec.validate(&value), Ok(()), "{value}");
+        }
+        for value in [json!(0), json!(0.99), json!(10.01), json!("11")] {
+            assert!(spec.validate(&value).is_err(), "{value}");
+        }
+        assert!(spec.validate(&json!("abc")).is_err());
+        assert!(spec.validate(&json!(true)).is_err());
+    }
+
+    #[test]
+    fn step() {
+        let spec = SchemaSpec {
+            min: Some(1.0),
+            step: Some(0.5),
+            ..Default::default()
+        };
+        for value in [json!(1), json!(1.5), json!(100)] {
+            assert_eq!(spec.validate(&value), Ok(()), "{value}");
+        }
+        assert!(spec.validate(&json!(1.25)).is_err());
+
+        // NOTE: the steps start from zero without `min`, tolerating the rounding errors
+        let spec = SchemaSpec {
+            step: Some(0.1),
+            ..Default::default()
+        };
+        assert_eq!(spec.validate(&json!(0.3)), Ok(()));
+        assert!(spec.validate(&json!(0.35)).is_err());
+    }
+
+    #[test]
+    fn required() {
+        let spec = SchemaSpec {
+            required: true,
+            ..Default::default()
+        };
+        assert!(spec.validate(&Value::Null).is_err());
+        assert!(spec.validate(&json!("  ")).is_err());
+        assert_eq!(SchemaSpec::default().validate(&Value::Null), Ok(()));
+    }
 }
@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A password or any other secret string.
+///
+/// The secrets are kept apart from the form data, and merged into it only when submitted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSpec {
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub min_length: Option<usize>,
+}
+
+impl SchemaSpec {
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        let length = match value {
+            Value::String(value) => value.chars().count(),
+            _ => 0,
+        };
+        if length == 0 {
+            return super::validate_required(self.required);
+        }
+
+        match self.min_length.filter(|&min| length < min) {
+            Some(min) => Err(format!("Should be at least {min} characters")),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,7 +1,276 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct SchemaSpec {
     #[serde(default)]
     pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub min_length: Option<usize>,
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// A regular expression which should match any part of the value,
+    /// so use `^...$` to match the whole value
+    #[serde(default)]
+    #[schemars(with = "Option<String>")]
+    pub pattern: Option<SchemaPattern>,
+    /// The error message shown if the pattern does not match
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub format: Option<SchemaStringFormat>,
+}
+
+impl SchemaSpec {
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        let value = match value {
+            Value::Null => Cow::Borrowed(""),
+            Value::String(value) => Cow::Borrowed(value.as_str()),
+            value => Cow::Owned(value.to_string()),
+        };
+        if value.is_empty() {
+            return super::validate_required(self.required);
+        }
+
+        let length = value.chars().count();
+        if let Some(min) = self.min_length.filter(|&min| length < min) {
+            return Err(format!("Should be at least {min} characters"));
+        }
+        if let Some(max) = self.max_length.filter(|&max| length > max) {
+            return Err(format!("Should be at most {max} characters"));
+        }
+        if let Some(pattern) = &self.pattern {
+            if !pattern.0.is_match(&value) {
+                return Err(self
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| format!("Should match the pattern: {pattern}")));
+            }
+        }
+        match self.format {
+            Some(format) => format.validate(&value),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A regular expression, which is compiled once when the schema is parsed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SchemaPattern(Regex);
+
+impl FromStr for SchemaPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Regex::new(s)
+            .map(Self)
+            .map_err(|error| format!("Invalid pattern: {error}"))
+    }
+}
+
+impl TryFrom<String> for SchemaPattern {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<SchemaPattern> for String {
+    fn from(value: SchemaPattern) -> Self {
+        value.0.as_str().into()
+    }
+}
+
+impl PartialEq for SchemaPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl fmt::Display for SchemaPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.as_str().fmt(f)
+    }
+}
+
+/// The well-known formats of the strings, mostly used by Kubernetes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaStringFormat {
+    /// A DNS-1123 label, such as the names of the namespaces and the services
+    Dns1123Label,
+    /// A DNS-1123 subdomain, such as the names of most resources
+    Dns1123Subdomain,
+    /// A Kubernetes quantity, such as `500m`, `2` or `31Gi`
+    Quantity,
+}
+
+impl SchemaStringFormat {
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            Self::Dns1123Label => {
+                if value.len() <= 63 && is_dns1123_label(value) {
+                    Ok(())
+                } else {
+                    Err("Should be at most 63 lowercase alphanumerics or '-', \
+                        starting and ending with an alphanumeric"
+                        .into())
+                }
+            }
+            Self::Dns1123Subdomain => {
+                if value.len() <= 253 && value.split('.').all(is_dns1123_label) {
+                    Ok(())
+                } else {
+                    Err(
+                        "Should be at most 253 lowercase alphanumerics, '-' or '.', \
+                        starting and ending with an alphanumeric"
+                            .into(),
+                    )
+                }
+            }
+            Self::Quantity => {
+                if is_quantity(value) {
+                    Ok(())
+                } else {
+                    Err("Should be a quantity, such as 500m, 2 or 31Gi".into())
+                }
+            }
+        }
+    }
+}
+
+fn is_dns1123_label(value: &str) -> bool {
+    let is_alphanumeric = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+
+    value.starts_with(is_alphanumeric)
+        && value.ends_with(is_alphanumeric)
+        && value.chars().all(|c| is_alphanumeric(c) || c == '-')
+}
+
+fn is_quantity(value: &str) -> bool {
+    let is_digits = |value: &str| value.chars().all(|c| c.is_ascii_digit());
+
+    let value = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let (number, suffix) = value.split_at(
+        value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len()),
+    );
+    let is_number = match number.split_once('.') {
+        Some((integer, fraction)) => {
+            !(integer.is_empty() && fraction.is_empty()) && is_digits(fraction)
+        }
+        None => !number.is_empty(),
+    };
+
+    is_number
+        && match suffix {
+            "" | "n" | "u" | "m" | "k" | "M" | "G" | "T" | "P" | "E" => true,
+            "Ki" | "Mi" | "Gi" | "Ti" | "Pi" | "Ei" => true,
+            suffix => suffix.strip_prefix(['e', 'E']).is_some_and(|exponent| {
+                let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+                !exponent.is_empty() && is_digits(exponent)
+            }),
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn dns1123_label() {
+        let format = SchemaStringFormat::Dns1123Label;
+        for value in ["a", "my-app", "0abc9", &"a".repeat(63)] {
+            assert_eq!(format.validate(value), Ok(()), "{value}");
+        }
+        for value in [
+            "",
+            "-a",
+            "a-",
+            "My-App",
+            "my_app",
+            "my.app",
+            &"a".repeat(64),
+        ] {
+            assert!(format.validate(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn dns1123_subdomain() {
+        let format = SchemaStringFormat::Dns1123Subdomain;
+        for value in ["a", "my-app", "my.app.example.com", &"a".repeat(253)] {
+            assert_eq!(format.validate(value), Ok(()), "{value}");
+        }
+        for value in ["", ".a", "a.", "a..b", "a.-b", "My.app", &"a".repeat(254)] {
+            assert!(format.validate(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn quantity() {
+        let format = SchemaStringFormat::Quantity;
+        for value in [
+            "0", "2", "+2", "-2", "1.5", ".5", "5.", "500m", "31Gi", "1k", "1e3", "1E-3", "2.5e+10",
+        ] {
+            assert_eq!(format.validate(value), Ok(()), "{value}");
+        }
+        for value in [
+            "", ".", "m", "1.2.3", "1 Gi", "1gi", "1Ki2", "1KiB", "1e", "1e+", "1e3.5", "--1",
+        ] {
+            assert!(format.validate(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn length() {
+        let spec = SchemaSpec {
+            min_length: Some(2),
+            max_length: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(spec.validate(&json!("ab")), Ok(()));
+        assert_eq!(spec.validate(&json!("abcd")), Ok(()));
+        // NOTE: the lengths are counted in characters, not in bytes
+        assert_eq!(spec.validate(&json!("가나다라")), Ok(()));
+        assert!(spec.validate(&json!("a")).is_err());
+        assert!(spec.validate(&json!("abcde")).is_err());
+
+        // NOTE: the empty values are checked by `required` instead
+        assert_eq!(spec.validate(&json!("")), Ok(()));
+        assert_eq!(spec.validate(&Value::Null), Ok(()));
+        let spec = SchemaSpec {
+            required: true,
+            ..spec
+        };
+        assert!(spec.validate(&json!("")).is_err());
+    }
+
+    #[test]
+    fn pattern() {
+        let spec: SchemaSpec = ::serde_json::from_value(json!({
+            "pattern": "^[a-z]+$",
+            "message": "Should be lowercase",
+        }))
+        .unwrap();
+        assert_eq!(spec.validate(&json!("abc")), Ok(()));
+        assert_eq!(
+            spec.validate(&json!("aBc")),
+            Err("Should be lowercase".into()),
+        );
+
+        assert!(::serde_json::from_value::<SchemaSpec>(json!({ "pattern": "[a-" })).is_err());
+    }
 }
@@ -0,0 +1,319 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use strum::{Display, EnumString};
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Display,
+    EnumString,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DataColumnType {
+    #[default]
+    String,
+    Integer,
+    Float,
+    Bool,
+    DateTime,
+    Json,
+}
+
+impl DataColumnType {
+    /// Guess the column type which can represent all the given values.
+    pub fn infer<'a>(values: impl IntoIterator<Item = &'a Value>) -> Self {
+        values
+            .into_iter()
+            .filter_map(Self::infer_value)
+            .try_fold(None, |inferred, next| match (inferred, next) {
+                (None, next) => Some(Some(next)),
+                (Some(last), next) if last == next => Some(Some(last)),
+                (Some(Self::Integer | Self::Float), Self::Integer | Self::Float) => {
+                    Some(Some(Self::Float))
+                }
+                _ => None,
+            })
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    fn infer_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(Self::Bool),
+            Value::Number(value) if value.is_f64() => Some(Self::Float),
+            Value::Number(_) => Some(Self::Integer),
+            // NOTE: the blank cells are missing values, as the empty ones are
+            Value::String(value) if value.trim().is_empty() => None,
+            Value::String(value) => Some(if parse_bool(value).is_some() {
+                Self::Bool
+            } else if parse_integer(value).is_some() {
+                Self::Integer
+            } else if parse_decimal(value).is_some() {
+                Self::Float
+            } else if parse_datetime(value).is_some() {
+                Self::DateTime
+            } else {
+                Self::String
+            }),
+            Value::Array(_) | Value::Object(_) => Some(Self::Json),
+        }
+    }
+
+    /// Convert the value into this type, or leave it as it is if not possible.
+    ///
+    /// The datetime values are normalized into RFC 3339 strings.
+    pub fn parse(&self, value: Value) -> Value {
+        match (self, value) {
+            (Self::String, Value::String(value)) => Value::String(value),
+            (Self::String, Value::Null) => Value::Null,
+            (Self::String, value) => Value::String(value.to_string()),
+            (_, Value::String(value)) if value.trim().is_empty() => Value::Null,
+            (Self::Integer, Value::String(value)) => match parse_integer(&value) {
+                Some(parsed) => Value::Number(parsed.into()),
+                None => Value::String(value),
+            },
+            (Self::Float, Value::String(value)) => {
+                let parsed = parse_decimal(&value)
+                    .or_else(|| parse_integer(&value).map(|parsed| parsed as f64))
+                    .and_then(Number::from_f64);
+                match parsed {
+                    Some(parsed) => Value::Number(parsed),
+                    None => Value::String(value),
+                }
+            }
+            (Self::Bool, Value::String(value)) => match parse_bool(&value) {
+                Some(parsed) => Value::Bool(parsed),
+                None => Value::String(value),
+            },
+            (Self::DateTime, Value::String(value)) => match parse_datetime(&value) {
+                Some(parsed) => Value::String(parsed.to_rfc3339()),
+                None => Value::String(value),
+            },
+            (Self::Json, Value::String(value)) => {
+                ::serde_json::from_str(&value).unwrap_or(Value::String(value))
+            }
+            (_, value) => value,
+        }
+    }
+}
+
+/// Parse a boolean string, such as `true` or `FALSE`.
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim() {
+        "true" | "True" | "TRUE" => Some(true),
+        "false" | "False" | "FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse an integer in its canonical form, such as `-12` but neither `012` nor `+12`.
+///
+/// The integers out of `i64` are not parsed, as they are usually the identifiers.
+pub fn parse_integer(value: &str) -> Option<i64> {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let is_canonical = match digits.as_bytes() {
+        [b'0'] => true,
+        [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    };
+    if is_canonical {
+        value.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Parse a decimal number in its canonical form, such as `-1.5` or `1e-3`.
+///
+/// The numbers without any fractions or exponents are integers, so they are not parsed.
+pub fn parse_decimal(value: &str) -> Option<f64> {
+    fn take_digits(value: &[u8]) -> (&[u8], &[u8]) {
+        let len = value.iter().take_while(|c| c.is_ascii_digit()).count();
+        value.split_at(len)
+    }
+
+    let rest = value.strip_prefix('-').unwrap_or(value).as_bytes();
+    let (integer, mut rest) = take_digits(rest);
+    if !matches!(integer, [b'0'] | [b'1'..=b'9', ..]) {
+        return None;
+    }
+
+    let mut has_fraction_or_exponent = false;
+    if let [b'.', fraction @ ..] = rest {
+        let (digits, next) = take_digits(fraction);
+        if digits.is_empty() {
+            return None;
+        }
+        has_fraction_or_exponent = true;
+        rest = next;
+    }
+    if let [b'e' | b'E', exponent @ ..] = rest {
+        let exponent = match exponent {
+            [b'+' | b'-', exponent @ ..] => exponent,
+            exponent => exponent,
+        };
+        let (digits, next) = take_digits(exponent);
+        if digits.is_empty() {
+            return None;
+        }
+        has_fraction_or_exponent = true;
+        rest = next;
+    }
+
+    if has_fraction_or_exponent && rest.is_empty() {
+        value.parse().ok().filter(|value: &f64| value.is_finite())
+    } else {
+        None
+    }
+}
+
+/// Parse a datetime string, such as RFC 3339 or a naive one in UTC.
+pub fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|value| value.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+                .into_iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+                .or_else(|| {
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })
+                .map(|value| value.and_utc())
+        })
+}
+
+/// Infer the types of the columns, preferring the given ones, and convert the records into them.
+pub(super) fn infer_types(
+    headers: &[String],
+    records: &mut [Vec<Value>],
+    overrides: &BTreeMap<String, DataColumnType>,
+) -> Vec<DataColumnType> {
+    let types: Vec<_> = headers
+        .iter()
+        .enumerate()
+        .map(|(index, name)| match overrides.get(name) {
+            Some(r#type) => *r#type,
+            None => DataColumnType::infer(records.iter().filter_map(|record| record.get(index))),
+        })
+        .collect();
+
+    for record in records.iter_mut() {
+        for (value, r#type) in record.iter_mut().zip(&types) {
+            *value = r#type.parse(value.take());
+        }
+    }
+    types
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse_integer("0"), Some(0));
+        assert_eq!(parse_integer("-0"), Some(0));
+        assert_eq!(parse_integer("-12"), Some(-12));
+        assert_eq!(parse_integer("9223372036854775807"), Some(i64::MAX));
+        assert_eq!(parse_integer("-9223372036854775808"), Some(i64::MIN));
+
+        // NOTE: the leading zeros and the out-of-range values are kept as the identifiers
+        assert_eq!(parse_integer("007"), None);
+        assert_eq!(parse_integer("-007"), None);
+        assert_eq!(parse_integer("9223372036854775808"), None);
+        assert_eq!(parse_integer("-9223372036854775809"), None);
+
+        for value in [
+            "", " ", "-", "+12", " 12", "12 ", "1e3", "1.0", "--1", "1_000",
+        ] {
+            assert_eq!(parse_integer(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn decimals() {
+        assert_eq!(parse_decimal("1.5"), Some(1.5));
+        assert_eq!(parse_decimal("-1.5"), Some(-1.5));
+        assert_eq!(parse_decimal("0.25"), Some(0.25));
+        assert_eq!(parse_decimal("1e3"), Some(1000.0));
+        assert_eq!(parse_decimal("1E+3"), Some(1000.0));
+        assert_eq!(parse_decimal("1.5e-3"), Some(0.0015));
+        assert_eq!(parse_decimal("-0.0"), Some(-0.0));
+
+        // NOTE: the integers are parsed by `parse_integer` instead
+        assert_eq!(parse_decimal("0"), None);
+        assert_eq!(parse_decimal("-0"), None);
+        assert_eq!(parse_decimal("9223372036854775808"), None);
+
+        for value in [
+            "", " ", "007.5", "01e3", ".5", "5.", "1e", "1e+", "1.5.0", " 1.5", "1.5 ", "+1.5",
+            "1e999", "NaN", "inf",
+        ] {
+            assert_eq!(parse_decimal(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn infer_numbers() {
+        let infer = |values: &[Value]| DataColumnType::infer(values);
+
+        assert_eq!(infer(&[json!("1"), json!("-0")]), DataColumnType::Integer);
+        assert_eq!(infer(&[json!("1"), json!("1e3")]), DataColumnType::Float);
+        assert_eq!(infer(&[json!("1"), json!("007")]), DataColumnType::String);
+        assert_eq!(
+            infer(&[json!("1"), json!("9223372036854775808")]),
+            DataColumnType::String,
+        );
+
+        // NOTE: the empty and blank cells are missing values, so they do not affect the types
+        assert_eq!(
+            infer(&[json!(""), json!("1"), json!("  "), Value::Null]),
+            DataColumnType::Integer,
+        );
+        assert_eq!(infer(&[json!(""), json!(" \t")]), DataColumnType::String);
+    }
+
+    #[test]
+    fn parse_numbers() {
+        let integer = DataColumnType::Integer;
+        assert_eq!(integer.parse(json!("-0")), json!(0));
+        assert_eq!(integer.parse(json!("007")), json!("007"));
+        assert_eq!(
+            integer.parse(json!("9223372036854775808")),
+            json!("9223372036854775808"),
+        );
+        assert_eq!(integer.parse(json!("")), Value::Null);
+        assert_eq!(integer.parse(json!("  ")), Value::Null);
+
+        let float = DataColumnType::Float;
+        assert_eq!(float.parse(json!("1e3")), json!(1000.0));
+        assert_eq!(float.parse(json!("2")), json!(2.0));
+        assert_eq!(float.parse(json!("007")), json!("007"));
+        assert_eq!(float.parse(json!(" ")), Value::Null);
+
+        // NOTE: the strings are kept as they are, including the blank ones
+        assert_eq!(DataColumnType::String.parse(json!("  ")), json!("  "));
+    }
+}
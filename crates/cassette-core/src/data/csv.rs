@@ -1,34 +1,61 @@
-use std::{io::Read, rc::Rc};
+use std::{collections::BTreeMap, io::Read, rc::Rc};
 
 use anyhow::Result;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::column::DataColumnType;
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct CsvTable {
     pub headers: Vec<String>,
+    #[serde(default)]
+    pub types: Vec<DataColumnType>,
     pub records: Rc<Vec<Vec<Value>>>,
 }
 
 impl CsvTable {
-    pub(super) fn from_reader(reader: impl Read) -> Result<Self> {
-        let mut reader = ::csv::Reader::from_reader(reader);
+    pub(super) fn from_reader(
+        reader: impl Read,
+        delimiter: u8,
+        overrides: &BTreeMap<String, DataColumnType>,
+    ) -> Result<Self> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(reader);
 
-        let headers = reader.headers()?.deserialize(None)?;
-        let records = reader
-            .into_deserialize()
-            .map(|record| record.map_err(Into::into))
-            .collect::<Result<_>>()
-            .map(Rc::new)?;
+        let headers: Vec<String> = reader.headers()?.deserialize(None)?;
+        let mut records = reader
+            .into_records()
+            .map(|record| {
+                record
+                    .map(|record| {
+                        record
+                            .iter()
+                            .map(|value| Value::String(value.into()))
+                            .collect()
+                    })
+                    .map_err(Into::into)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let types = super::column::infer_types(&headers, &mut records, overrides);
 
-        Ok(Self { headers, records })
+        Ok(Self {
+            headers,
+            types,
+            records: Rc::new(records),
+        })
     }
 
     pub fn columns(&self) -> Vec<String> {
         self.headers.clone()
     }
 
+    pub fn column_types(&self) -> Vec<DataColumnType> {
+        self.types.clone()
+    }
+
     pub fn first_row(&self) -> Option<Vec<Value>> {
         self.records.first().cloned()
     }
@@ -0,0 +1,148 @@
+use anyhow::{bail, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use strum::{Display, EnumString};
+
+use super::{json::JsonTable, table::DataTableSource};
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Display,
+    EnumString,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum DataExportType {
+    Csv,
+    Json,
+    Ndjson,
+    Tsv,
+    Xlsx,
+    Yaml,
+}
+
+impl DataExportType {
+    /// The file extension of the given data type.
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => ".csv",
+            Self::Json => ".json",
+            Self::Ndjson => ".ndjson",
+            Self::Tsv => ".tsv",
+            Self::Xlsx => ".xlsx",
+            Self::Yaml => ".yaml",
+        }
+    }
+
+    /// The MIME type of the given data type.
+    pub const fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Json => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Tsv => "text/tab-separated-values",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Yaml => "application/yaml",
+        }
+    }
+
+    /// Serialize the table, whose rows are encoded as objects unless tabular.
+    ///
+    /// The raw tables are exported as they are.
+    pub fn export_table(&self, name: &str, table: &DataTableSource) -> Result<Vec<u8>> {
+        if let DataTableSource::Raw(data) = table {
+            return Ok(data.clone());
+        }
+
+        let headers = table.columns()?;
+        let records = table.records()?;
+        match self {
+            Self::Csv | Self::Tsv => {
+                let delimiter = match self {
+                    Self::Tsv => b'\t',
+                    _ => b',',
+                };
+                let mut writer = ::csv::WriterBuilder::new()
+                    .delimiter(delimiter)
+                    .from_writer(Vec::default());
+                writer.write_record(&headers)?;
+                for record in records.iter() {
+                    writer.write_record(record.iter().map(|value| match value {
+                        Value::Null => String::default(),
+                        Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    }))?;
+                }
+                writer
+                    .into_inner()
+                    .map_err(|error| error.into_error().into())
+            }
+            Self::Json | Self::Ndjson | Self::Yaml => {
+                let objects: Vec<_> = records
+                    .iter()
+                    .map(|record| {
+                        Value::Object(
+                            headers
+                                .iter()
+                                .cloned()
+                                .zip(record.iter().cloned())
+                                .collect::<Map<_, _>>(),
+                        )
+                    })
+                    .collect();
+                self.export_value(&Value::Array(objects))
+            }
+            #[cfg(feature = "xlsx")]
+            Self::Xlsx => super::xlsx::to_bytes(name, &headers, &records),
+            #[cfg(not(feature = "xlsx"))]
+            Self::Xlsx => {
+                let _ = name;
+                bail!("Unsupported data type: {self}")
+            }
+        }
+    }
+
+    /// Serialize any value, such as a task state.
+    ///
+    /// The tabular types require an array of objects.
+    pub fn export_value(&self, value: &Value) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => ::serde_json::to_vec_pretty(value).map_err(Into::into),
+            Self::Ndjson => match value {
+                Value::Array(values) => {
+                    let mut buf = Vec::default();
+                    for value in values {
+                        ::serde_json::to_writer(&mut buf, value)?;
+                        buf.push(b'\n');
+                    }
+                    Ok(buf)
+                }
+                _ => bail!("NDJSON data should be an array"),
+            },
+            #[cfg(feature = "yaml")]
+            Self::Yaml => ::serde_yml::to_string(value)
+                .map(String::into_bytes)
+                .map_err(Into::into),
+            #[cfg(not(feature = "yaml"))]
+            Self::Yaml => bail!("Unsupported data type: {self}"),
+            Self::Csv | Self::Tsv | Self::Xlsx => match value {
+                Value::Array(objects) => {
+                    let table = JsonTable::from_objects(objects.clone(), &Default::default())?;
+                    self.export_table("Sheet1", &DataTableSource::Json(table))
+                }
+                _ => bail!("Tabular data should be an array of objects"),
+            },
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use std::{collections::HashMap, rc::Rc};
+
+use anyhow::{bail, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::{Display, EnumString};
+
+use super::{column::DataColumnType, csv::CsvTable, table::DataTableSource};
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Display,
+    EnumString,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum DataTableJoinKind {
+    /// Keep the rows matched in both tables
+    #[default]
+    Inner,
+    /// Keep all the rows of the left table, filling the unmatched ones with nulls
+    Left,
+    /// Keep all the rows of both tables, filling the unmatched ones with nulls
+    Outer,
+}
+
+/// A pair of the key columns to match.
+///
+/// Each key is either a column name, or a JSON pointer into the row,
+/// such as `/metadata/labels/app.kubernetes.io~1instance`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableJoinKey {
+    pub left: String,
+    pub right: String,
+}
+
+struct Table {
+    headers: Vec<String>,
+    types: Vec<DataColumnType>,
+    records: Rc<Vec<Vec<Value>>>,
+}
+
+impl Table {
+    fn new(source: &DataTableSource) -> Result<Self> {
+        Ok(Self {
+            headers: source.columns()?,
+            types: source.column_types()?,
+            records: source.records()?,
+        })
+    }
+
+    /// Find the column of the key, returning the remaining JSON pointer, if any.
+    fn key<'a>(&self, key: &'a str) -> Result<(usize, Option<&'a str>)> {
+        let (column, pointer) = match key.strip_prefix('/') {
+            Some(pointer) => match pointer.find('/') {
+                Some(index) => (&pointer[..index], Some(&pointer[index..])),
+                None => (pointer, None),
+            },
+            None => (key, None),
+        };
+        let column = column.replace("~1", "/").replace("~0", "~");
+
+        match self.headers.iter().position(|header| *header == column) {
+            Some(index) => Ok((index, pointer)),
+            None => bail!("No such column: {column:?}"),
+        }
+    }
+}
+
+fn get_key(record: &[Value], keys: &[(usize, Option<&str>)]) -> Option<String> {
+    let values = keys
+        .iter()
+        .map(|(index, pointer)| {
+            let value = record.get(*index)?;
+            match pointer {
+                Some(pointer) => value.pointer(pointer),
+                None => Some(value),
+            }
+            .filter(|value| !value.is_null())
+            .cloned()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(Value::Array(values).to_string())
+}
+
+pub(super) fn join(
+    left: &DataTableSource,
+    right: &DataTableSource,
+    right_name: &str,
+    kind: DataTableJoinKind,
+    on: &[DataTableJoinKey],
+) -> Result<DataTableSource> {
+    if on.is_empty() {
+        bail!("Join requires at least one key")
+    }
+
+    let left = Table::new(left)?;
+    let right = Table::new(right)?;
+
+    let left_keys = on
+        .iter()
+        .map(|key| left.key(&key.left))
+        .collect::<Result<Vec<_>>>()?;
+    let right_keys = on
+        .iter()
+        .map(|key| right.key(&key.right))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut matches: HashMap<_, Vec<_>> = HashMap::new();
+    for (index, record) in right.records.iter().enumerate() {
+        // NOTE: nulls never match
+        if let Some(key) = get_key(record, &right_keys) {
+            matches.entry(key).or_default().push(index);
+        }
+    }
+
+    let mut records = Vec::default();
+    let mut is_matched = vec![false; right.records.len()];
+    let empty_left = vec![Value::Null; left.headers.len()];
+    let empty_right = vec![Value::Null; right.headers.len()];
+    for record in left.records.iter() {
+        let matched = get_key(record, &left_keys).and_then(|key| matches.get(&key));
+        match (matched, kind) {
+            (Some(matched), _) => {
+                for &index in matched {
+                    is_matched[index] = true;
+                    records.push([record.as_slice(), right.records[index].as_slice()].concat());
+                }
+            }
+            (None, DataTableJoinKind::Inner) => continue,
+            (None, DataTableJoinKind::Left | DataTableJoinKind::Outer) => {
+                records.push([record.as_slice(), empty_right.as_slice()].concat())
+            }
+        }
+    }
+    if kind == DataTableJoinKind::Outer {
+        records.extend(
+            right
+                .records
+                .iter()
+                .zip(is_matched)
+                .filter(|(_, is_matched)| !is_matched)
+                .map(|(record, _)| [empty_left.as_slice(), record.as_slice()].concat()),
+        );
+    }
+
+    // NOTE: the duplicated columns of the right table are prefixed by its name
+    let headers = left
+        .headers
+        .iter()
+        .cloned()
+        .chain(right.headers.iter().map(|header| {
+            if left.headers.contains(header) {
+                format!("{right_name}.{header}")
+            } else {
+                header.clone()
+            }
+        }))
+        .collect();
+    let types = left.types.iter().chain(&right.types).copied().collect();
+
+    Ok(DataTableSource::Csv(CsvTable {
+        headers,
+        types,
+        records: Rc::new(records),
+    }))
+}
+
+pub(super) fn union<'a>(
+    sources: impl IntoIterator<Item = &'a DataTableSource>,
+) -> Result<DataTableSource> {
+    let mut headers: Vec<String> = Vec::default();
+    let mut types: Vec<Option<DataColumnType>> = Vec::default();
+    let mut tables = Vec::default();
+    for source in sources {
+        let table = Table::new(source)?;
+        let indices: Vec<_> = table
+            .headers
+            .iter()
+            .zip(&table.types)
+            .map(
+                |(header, r#type)| match headers.iter().position(|h| h == header) {
+                    Some(index) => {
+                        // NOTE: the conflicted types are inferred again from the values
+                        if types[index] != Some(*r#type) {
+                            types[index] = None;
+                        }
+                        index
+                    }
+                    None => {
+                        headers.push(header.clone());
+                        types.push(Some(*r#type));
+                        headers.len() - 1
+                    }
+                },
+            )
+            .collect();
+        tables.push((indices, table.records));
+    }
+
+    let mut records = Vec::default();
+    for (indices, table) in tables {
+        for record in table.iter() {
+            let mut aligned = vec![Value::Null; headers.len()];
+            for (index, value) in indices.iter().zip(record) {
+                aligned[*index] = value.clone();
+            }
+            records.push(aligned);
+        }
+    }
+
+    let types = types
+        .into_iter()
+        .enumerate()
+        .map(|(index, r#type)| match r#type {
+            Some(r#type) => r#type,
+            None => {
+                let r#type =
+                    DataColumnType::infer(records.iter().filter_map(|record| record.get(index)));
+                for record in &mut records {
+                    record[index] = r#type.parse(record[index].take());
+                }
+                r#type
+            }
+        })
+        .collect();
+
+    Ok(DataTableSource::Csv(CsvTable {
+        headers,
+        types,
+        records: Rc::new(records),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn table(headers: &[&str], records: Vec<Vec<Value>>) -> DataTableSource {
+        let types = (0..headers.len())
+            .map(|index| DataColumnType::infer(records.iter().map(|record| &record[index])))
+            .collect();
+        DataTableSource::Csv(CsvTable {
+            headers: headers.iter().map(|&header| header.into()).collect(),
+            types,
+            records: Rc::new(records),
+        })
+    }
+
+    fn users() -> DataTableSource {
+        table(
+            &["id", "name"],
+            vec![
+                vec![json!(1), json!("alice")],
+                vec![json!(2), json!("bob")],
+                vec![Value::Null, json!("carol")],
+            ],
+        )
+    }
+
+    fn orders() -> DataTableSource {
+        table(
+            &["id", "item"],
+            vec![
+                vec![json!(1), json!("apple")],
+                vec![json!(1), json!("banana")],
+                vec![json!(4), json!("cherry")],
+                vec![Value::Null, json!("durian")],
+            ],
+        )
+    }
+
+    fn on_id() -> Vec<DataTableJoinKey> {
+        vec![DataTableJoinKey {
+            left: "id".into(),
+            right: "id".into(),
+        }]
+    }
+
+    #[test]
+    fn inner_join() {
+        let joined = join(
+            &users(),
+            &orders(),
+            "orders",
+            DataTableJoinKind::Inner,
+            &on_id(),
+        )
+        .unwrap();
+
+        // NOTE: the duplicated columns of the right table are prefixed by its name
+        assert_eq!(
+            joined.columns().unwrap(),
+            ["id", "name", "orders.id", "item"]
+        );
+        assert_eq!(
+            joined.column_types().unwrap(),
+            [
+                DataColumnType::Integer,
+                DataColumnType::String,
+                DataColumnType::Integer,
+                DataColumnType::String,
+            ],
+        );
+        assert_eq!(
+            *joined.records().unwrap(),
+            [
+                vec![json!(1), json!("alice"), json!(1), json!("apple")],
+                vec![json!(1), json!("alice"), json!(1), json!("banana")],
+            ],
+        );
+    }
+
+    #[test]
+    fn left_join() {
+        let joined = join(
+            &users(),
+            &orders(),
+            "orders",
+            DataTableJoinKind::Left,
+            &on_id(),
+        )
+        .unwrap();
+
+        // NOTE: the nulls never match, even with the other nulls
+        assert_eq!(
+            *joined.records().unwrap(),
+            [
+                vec![json!(1), json!("alice"), json!(1), json!("apple")],
+                vec![json!(1), json!("alice"), json!(1), json!("banana")],
+                vec![json!(2), json!("bob"), Value::Null, Value::Null],
+                vec![Value::Null, json!("carol"), Value::Null, Value::Null],
+            ],
+        );
+    }
+
+    #[test]
+    fn outer_join() {
+        let joined = join(
+            &users(),
+            &orders(),
+            "orders",
+            DataTableJoinKind::Outer,
+            &on_id(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            *joined.records().unwrap(),
+            [
+                vec![json!(1), json!("alice"), json!(1), json!("apple")],
+                vec![json!(1), json!("alice"), json!(1), json!("banana")],
+                vec![json!(2), json!("bob"), Value::Null, Value::Null],
+                vec![Value::Null, json!("carol"), Value::Null, Value::Null],
+                vec![Value::Null, Value::Null, json!(4), json!("cherry")],
+                vec![Value::Null, Value::Null, Value::Null, json!("durian")],
+            ],
+        );
+    }
+
+    #[test]
+    fn join_on_pointer() {
+        let pods = table(
+            &["name", "metadata"],
+            vec![
+                vec![
+                    json!("web-0"),
+                    json!({ "labels": { "app.kubernetes.io/instance": "web" } }),
+                ],
+                vec![json!("db-0"), json!({ "labels": {} })],
+            ],
+        );
+        let releases = table(
+            &["name", "status"],
+            vec![vec![json!("web"), json!("deployed")]],
+        );
+        let on = [DataTableJoinKey {
+            left: "/metadata/labels/app.kubernetes.io~1instance".into(),
+            right: "name".into(),
+        }];
+
+        let joined = join(&pods, &releases, "releases", DataTableJoinKind::Inner, &on).unwrap();
+        assert_eq!(
+            joined.columns().unwrap(),
+            ["name", "metadata", "releases.name", "status"],
+        );
+        assert_eq!(joined.len(), 1);
+        assert_eq!(joined.records().unwrap()[0][3], json!("deployed"));
+    }
+
+    #[test]
+    fn join_errors() {
+        let kind = DataTableJoinKind::Inner;
+        assert!(join(&users(), &orders(), "orders", kind, &[]).is_err());
+
+        let on = [DataTableJoinKey {
+            left: "id".into(),
+            right: "user".into(),
+        }];
+        assert!(join(&users(), &orders(), "orders", kind, &on).is_err());
+    }
+
+    #[test]
+    fn union_mismatched_headers() {
+        let first = table(&["a", "b"], vec![vec![json!(1), json!("x")]]);
+        let second = table(&["c", "b"], vec![vec![json!(true), json!(2)]]);
+
+        let merged = union([&first, &second]).unwrap();
+        assert_eq!(merged.columns().unwrap(), ["a", "b", "c"]);
+
+        // NOTE: the conflicted types are inferred again from all the values
+        assert_eq!(
+            merged.column_types().unwrap(),
+            [
+                DataColumnType::Integer,
+                DataColumnType::String,
+                DataColumnType::Bool,
+            ],
+        );
+        assert_eq!(
+            *merged.records().unwrap(),
+            [
+                vec![json!(1), json!("x"), Value::Null],
+                vec![Value::Null, json!("2"), json!(true)],
+            ],
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::{collections::BTreeMap, io::Read, rc::Rc};
+
+use anyhow::{bail, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::column::DataColumnType;
+
+/// A table of JSON objects, whose columns are the union of their keys.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonTable {
+    pub headers: Vec<String>,
+    #[serde(default)]
+    pub types: Vec<DataColumnType>,
+    pub records: Rc<Vec<Vec<Value>>>,
+}
+
+impl JsonTable {
+    /// Parse an array of objects.
+    pub(super) fn from_reader(
+        reader: impl Read,
+        overrides: &BTreeMap<String, DataColumnType>,
+    ) -> Result<Self> {
+        match ::serde_json::from_reader(reader)? {
+            Value::Array(objects) => Self::from_objects(objects, overrides),
+            _ => bail!("JSON data table should be an array of objects"),
+        }
+    }
+
+    /// Parse the newline-delimited objects.
+    pub(super) fn from_ndjson_reader(
+        reader: impl Read,
+        overrides: &BTreeMap<String, DataColumnType>,
+    ) -> Result<Self> {
+        let objects = ::serde_json::Deserializer::from_reader(reader)
+            .into_iter::<Value>()
+            .collect::<Result<_, _>>()?;
+        Self::from_objects(objects, overrides)
+    }
+
+    /// Collect the objects, such as the Kubernetes resources.
+    pub fn from_objects(
+        objects: Vec<Value>,
+        overrides: &BTreeMap<String, DataColumnType>,
+    ) -> Result<Self> {
+        let mut headers: Vec<String> = Vec::default();
+        let objects = objects
+            .into_iter()
+            .enumerate()
+            .map(|(index, object)| match object {
+                Value::Object(map) => {
+                    for key in map.keys() {
+                        if !headers.contains(key) {
+                            headers.push(key.clone());
+                        }
+                    }
+                    Ok(map)
+                }
+                _ => bail!("JSON record #{index} should be an object"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut records = objects
+            .into_iter()
+            .map(|mut map| {
+                headers
+                    .iter()
+                    .map(|key| map.remove(key).unwrap_or_default())
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let types = super::column::infer_types(&headers, &mut records, overrides);
+
+        Ok(Self {
+            headers,
+            types,
+            records: Rc::new(records),
+        })
+    }
+
+    pub fn columns(&self) -> Vec<String> {
+        self.headers.clone()
+    }
+
+    pub fn column_types(&self) -> Vec<DataColumnType> {
+        self.types.clone()
+    }
+
+    pub fn first_row(&self) -> Option<Vec<Value>> {
+        self.records.first().cloned()
+    }
+
+    pub fn records(&self) -> Rc<Vec<Vec<Value>>> {
+        self.records.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+}
@@ -1,3 +1,12 @@
 pub mod actor;
+pub mod column;
 pub mod csv;
+pub mod export;
+pub mod join;
+pub mod json;
+pub mod selection;
 pub mod table;
+pub mod transform;
+pub mod view;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
@@ -0,0 +1,262 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// The selected rows of a table, stored as a bitset.
+///
+/// It is serialized as the ranges of the selected rows,
+/// so that selecting all the rows of a large table stays small.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "DataTableSelectionRepr", into = "DataTableSelectionRepr")]
+pub struct DataTableSelection {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl DataTableSelection {
+    const BITS: usize = u64::BITS as usize;
+
+    /// Create an empty selection of the given number of rows.
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(Self::BITS)],
+        }
+    }
+
+    /// The number of the rows, including the unselected ones.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of the selected rows.
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.len && self.words[index / Self::BITS] & Self::mask(index) != 0
+    }
+
+    /// Select or deselect the row, ignoring the out-of-range ones.
+    pub fn set(&mut self, index: usize, selected: bool) {
+        if index < self.len {
+            let word = &mut self.words[index / Self::BITS];
+            if selected {
+                *word |= Self::mask(index);
+            } else {
+                *word &= !Self::mask(index);
+            }
+        }
+    }
+
+    /// Select or deselect all the given rows.
+    pub fn set_many(&mut self, indices: impl IntoIterator<Item = usize>, selected: bool) {
+        for index in indices {
+            self.set(index, selected)
+        }
+    }
+
+    /// Select or deselect all the rows.
+    pub fn set_all(&mut self, selected: bool) {
+        self.words.fill(if selected { u64::MAX } else { 0 });
+        self.clear_tail();
+    }
+
+    /// Change the number of the rows, deselecting the new ones.
+    pub fn resize(&mut self, len: usize) {
+        self.len = len;
+        self.words.resize(len.div_ceil(Self::BITS), 0);
+        self.clear_tail();
+    }
+
+    /// The first selected row.
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    /// Iterate the selected rows in order, skipping the empty words.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = usize> {
+        self.words
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| **word != 0)
+            .flat_map(|(offset, &word)| {
+                let mut word = word;
+                ::std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(offset * Self::BITS + bit)
+                })
+            })
+    }
+
+    /// Iterate the contiguous ranges of the selected rows.
+    pub fn ranges(&self) -> impl '_ + Iterator<Item = Range<usize>> {
+        let mut indices = self.iter().peekable();
+        ::std::iter::from_fn(move || {
+            let start = indices.next()?;
+            let mut end = start + 1;
+            while indices.next_if_eq(&end).is_some() {
+                end += 1;
+            }
+            Some(start..end)
+        })
+    }
+
+    const fn mask(index: usize) -> u64 {
+        1 << (index % Self::BITS)
+    }
+
+    fn clear_tail(&mut self) {
+        let tail = self.len % Self::BITS;
+        if tail != 0 {
+            if let Some(word) = self.words.last_mut() {
+                *word &= (1 << tail) - 1;
+            }
+        }
+    }
+}
+
+/// The serialized form of [`DataTableSelection`].
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DataTableSelectionRepr {
+    Ranges { len: usize, ranges: Vec<[usize; 2]> },
+    // NOTE: the selections had been stored as flags before
+    Flags(Vec<bool>),
+}
+
+impl DataTableSelectionRepr {
+    /// Decode the selection of at most the given number of rows, ignoring the others.
+    ///
+    /// The persisted selections may come from the URL query,
+    /// so their length should be clamped before being allocated.
+    pub fn into_selection(self, max_len: usize) -> DataTableSelection {
+        match self {
+            Self::Ranges { len, ranges } => {
+                let len = len.min(max_len);
+                let mut selection = DataTableSelection::new(len);
+                for [start, end] in ranges {
+                    selection.set_many(start.min(len)..end.min(len), true);
+                }
+                selection
+            }
+            Self::Flags(flags) => {
+                let mut selection = DataTableSelection::new(flags.len().min(max_len));
+                for (index, selected) in flags.into_iter().take(max_len).enumerate() {
+                    selection.set(index, selected);
+                }
+                selection
+            }
+        }
+    }
+}
+
+impl From<DataTableSelectionRepr> for DataTableSelection {
+    fn from(repr: DataTableSelectionRepr) -> Self {
+        repr.into_selection(usize::MAX)
+    }
+}
+
+impl From<DataTableSelection> for DataTableSelectionRepr {
+    fn from(selection: DataTableSelection) -> Self {
+        Self::Ranges {
+            len: selection.len,
+            ranges: selection
+                .ranges()
+                .map(|range| [range.start, range.end])
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn ranges_round_trip() {
+        let mut selection = DataTableSelection::new(200);
+        selection.set_many([0, 1, 2, 63, 64, 65, 130], true);
+        selection.set_many(150..200, true);
+
+        let value = ::serde_json::to_value(&selection).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "len": 200,
+                "ranges": [[0, 3], [63, 66], [130, 131], [150, 200]],
+            }),
+        );
+
+        let decoded: DataTableSelection = ::serde_json::from_value(value).unwrap();
+        assert_eq!(decoded, selection);
+        assert_eq!(decoded.count(), 57);
+        assert_eq!(decoded.first(), Some(0));
+    }
+
+    #[test]
+    fn all_and_none_round_trip() {
+        for selected in [false, true] {
+            let mut selection = DataTableSelection::new(100);
+            selection.set_all(selected);
+
+            let value = ::serde_json::to_value(&selection).unwrap();
+            let decoded: DataTableSelection = ::serde_json::from_value(value).unwrap();
+            assert_eq!(decoded, selection);
+            assert_eq!(decoded.count(), if selected { 100 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn decode_flags() {
+        let decoded: DataTableSelection =
+            ::serde_json::from_value(json!([true, false, true])).unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded.iter().collect::<Vec<_>>(), [0, 2]);
+    }
+
+    #[test]
+    fn clamp_persisted() {
+        let repr: DataTableSelectionRepr = ::serde_json::from_value(json!({
+            "len": usize::MAX,
+            "ranges": [[1, 3], [8, usize::MAX], [usize::MAX - 1, usize::MAX]],
+        }))
+        .unwrap();
+        let selection = repr.into_selection(10);
+        assert_eq!(selection.len(), 10);
+        assert_eq!(selection.iter().collect::<Vec<_>>(), [1, 2, 8, 9]);
+
+        let repr: DataTableSelectionRepr =
+            ::serde_json::from_value(json!([false, true, true, true])).unwrap();
+        let selection = repr.into_selection(2);
+        assert_eq!(selection.len(), 2);
+        assert_eq!(selection.iter().collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn resize() {
+        let mut selection = DataTableSelection::new(70);
+        selection.set_all(true);
+        selection.resize(65);
+        assert_eq!(selection.count(), 65);
+
+        // NOTE: the new rows are not selected
+        selection.resize(130);
+        assert_eq!(selection.count(), 65);
+        assert!(!selection.contains(65));
+    }
+}
@@ -1,12 +1,15 @@
-use std::rc::Rc;
+use std::{collections::BTreeMap, rc::Rc};
 
 use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use strum::{Display, EnumString};
 use uuid::Uuid;
 
+use super::column::DataColumnType;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DataTable<Data = Rc<DataTableSource>> {
     pub name: String,
@@ -15,40 +18,160 @@ pub struct DataTable<Data = Rc<DataTableSource>> {
     pub log: DataTableLog,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+/// The provenance of a data table.
+///
+/// The id of a derived table is computed from its parents and its records,
+/// or anything else determining them such as a view,
+/// so the downstream tasks can tell whether their input has changed
+/// just by comparing the ids.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct DataTableLog {
-    id: Uuid,
-    version: u64,
+    pub id: Uuid,
+    #[serde(default)]
+    pub kind: DataTableLogKind,
+    #[serde(default)]
+    pub parents: Vec<Uuid>,
+    #[serde(default)]
+    pub version: u64,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
 }
 
-impl Default for DataTableLog {
-    fn default() -> Self {
+impl DataTableLog {
+    /// Create a log of a brand-new table.
+    pub fn new(kind: DataTableLogKind) -> Self {
         Self {
             id: Uuid::new_v4(),
+            kind,
+            parents: Vec::default(),
             version: 0,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Create a log of the table derived from the parents.
+    ///
+    /// The `data` may be the records, or anything determining them
+    /// from the parents, which is cheaper to hash for the large tables.
+    pub fn derive<'a>(
+        kind: DataTableLogKind,
+        parents: impl IntoIterator<Item = &'a Self>,
+        data: &(impl ?Sized + Serialize),
+    ) -> Result<Self> {
+        let parents: Vec<_> = parents.into_iter().collect();
+
+        let mut name = kind.to_string().into_bytes();
+        for parent in &parents {
+            name.extend_from_slice(parent.id.as_bytes());
+        }
+        ::serde_json::to_writer(&mut name, data)?;
+
+        Ok(Self {
+            id: Uuid::new_v5(&Uuid::NAMESPACE_OID, &name),
+            kind,
+            parents: parents.iter().map(|parent| parent.id).collect(),
+            version: parents
+                .iter()
+                .map(|parent| parent.version + 1)
+                .max()
+                .unwrap_or_default(),
+            updated_at: Utc::now(),
+        })
+    }
+
+    /// Keep the previous log if nothing has changed, or bump its version otherwise.
+    pub fn follow(self, previous: Option<&Self>) -> Self {
+        match previous {
+            Some(previous) if previous.id == self.id => previous.clone(),
+            Some(previous) => Self {
+                version: self.version.max(previous.version + 1),
+                ..self
+            },
+            None => self,
         }
     }
+
+    /// Return `true` if the table has been derived from the given one.
+    pub fn is_derived_from(&self, parent: &Self) -> bool {
+        self.parents.contains(&parent.id)
+    }
+}
+
+impl Default for DataTableLog {
+    fn default() -> Self {
+        Self::new(DataTableLogKind::default())
+    }
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Display,
+    EnumString,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum DataTableLogKind {
+    #[default]
+    Created,
+    Uploaded,
+    Loaded,
+    Selected,
+    Filtered,
+    Transformed,
+    Joined,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "kind", content = "data", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DataTableSource {
     Csv(super::csv::CsvTable),
+    Json(super::json::JsonTable),
     Raw(Vec<u8>),
+    #[cfg(feature = "xlsx")]
+    Xlsx(super::xlsx::XlsxTable),
 }
 
 impl DataTableSource {
     pub fn columns(&self) -> Result<Vec<String>> {
         match self {
             DataTableSource::Csv(data) => Ok(data.columns()),
+            DataTableSource::Json(data) => Ok(data.columns()),
             DataTableSource::Raw(_) => bail!("Raw data table has no columns"),
+            #[cfg(feature = "xlsx")]
+            DataTableSource::Xlsx(data) => Ok(data.columns()),
         }
     }
 
+    /// Get the types of the columns, filling the unknown ones with strings.
+    pub fn column_types(&self) -> Result<Vec<DataColumnType>> {
+        let mut types = match self {
+            DataTableSource::Csv(data) => data.column_types(),
+            DataTableSource::Json(data) => data.column_types(),
+            DataTableSource::Raw(_) => bail!("Raw data table has no columns"),
+            #[cfg(feature = "xlsx")]
+            DataTableSource::Xlsx(data) => data.column_types(),
+        };
+        types.resize(self.columns()?.len(), DataColumnType::default());
+        Ok(types)
+    }
+
     pub fn first_row(&self) -> Result<Option<Vec<Value>>> {
         match self {
             DataTableSource::Csv(data) => Ok(data.first_row()),
+            DataTableSource::Json(data) => Ok(data.first_row()),
             DataTableSource::Raw(_) => bail!("Raw data table has no records"),
+            #[cfg(feature = "xlsx")]
+            DataTableSource::Xlsx(data) => Ok(data.first_row()),
         }
     }
 
@@ -69,21 +192,46 @@ impl DataTableSource {
     pub fn records(&self) -> Result<Rc<Vec<Vec<Value>>>> {
         match self {
             DataTableSource::Csv(data) => Ok(data.records()),
+            DataTableSource::Json(data) => Ok(data.records()),
             DataTableSource::Raw(_) => bail!("Raw data table has no records"),
+            #[cfg(feature = "xlsx")]
+            DataTableSource::Xlsx(data) => Ok(data.records()),
         }
     }
 
+    /// Join the rows of the tables matched on the given keys.
+    pub fn join(
+        &self,
+        other: &Self,
+        other_name: &str,
+        kind: super::join::DataTableJoinKind,
+        on: &[super::join::DataTableJoinKey],
+    ) -> Result<Self> {
+        super::join::join(self, other, other_name, kind, on)
+    }
+
+    /// Concatenate the rows of the tables, aligning their columns by name.
+    pub fn union<'a>(sources: impl IntoIterator<Item = &'a Self>) -> Result<Self> {
+        super::join::union(sources)
+    }
+
     pub fn is_empty(&self) -> bool {
         match self {
             DataTableSource::Csv(data) => data.is_empty(),
+            DataTableSource::Json(data) => data.is_empty(),
             DataTableSource::Raw(data) => data.is_empty(),
+            #[cfg(feature = "xlsx")]
+            DataTableSource::Xlsx(data) => data.is_empty(),
         }
     }
 
     pub fn len(&self) -> usize {
         match self {
             DataTableSource::Csv(data) => data.len(),
+            DataTableSource::Json(data) => data.len(),
             DataTableSource::Raw(data) => data.len(),
+            #[cfg(feature = "xlsx")]
+            DataTableSource::Xlsx(data) => data.len(),
         }
     }
 }
@@ -106,19 +254,76 @@ impl DataTableSource {
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[strum(serialize_all = "kebab-case")]
 pub enum DataTableSourceType {
-    Cdl,
     Csv,
+    Json,
+    Ndjson,
     Raw,
+    Tsv,
+    Xlsx,
 }
 
 impl DataTableSourceType {
-    pub fn parse_bytes(&self, bytes: Vec<u8>) -> Result<DataTableSource> {
+    /// The file extensions of the given data type, if known.
+    pub const fn extensions(&self) -> &'static [&'static str] {
         match self {
-            Self::Cdl => bail!("Unsupported data type: {self}"),
-            Self::Csv => {
-                super::csv::CsvTable::from_reader(bytes.as_slice()).map(DataTableSource::Csv)
+            Self::Csv => &[".csv"],
+            Self::Json => &[".json"],
+            Self::Ndjson => &[".ndjson", ".jsonl"],
+            Self::Raw => &[],
+            Self::Tsv => &[".tsv", ".tab"],
+            Self::Xlsx => &[".xlsx"],
+        }
+    }
+
+    pub fn parse_bytes(
+        &self,
+        bytes: Vec<u8>,
+        options: &DataTableSourceOptions,
+    ) -> Result<DataTableSource> {
+        match self {
+            Self::Csv | Self::Tsv => {
+                let delimiter = match (self, options.delimiter) {
+                    (_, Some(delimiter)) if delimiter.is_ascii() => delimiter as u8,
+                    (_, Some(delimiter)) => bail!("Unsupported delimiter: {delimiter:?}"),
+                    (Self::Tsv, None) => b'\t',
+                    (_, None) => b',',
+                };
+                super::csv::CsvTable::from_reader(bytes.as_slice(), delimiter, &options.columns)
+                    .map(DataTableSource::Csv)
+            }
+            Self::Json => super::json::JsonTable::from_reader(bytes.as_slice(), &options.columns)
+                .map(DataTableSource::Json),
+            Self::Ndjson => {
+                super::json::JsonTable::from_ndjson_reader(bytes.as_slice(), &options.columns)
+                    .map(DataTableSource::Json)
             }
             Self::Raw => Ok(DataTableSource::Raw(bytes)),
+            #[cfg(feature = "xlsx")]
+            Self::Xlsx => super::xlsx::XlsxTable::from_bytes(
+                bytes,
+                options.sheet.as_deref(),
+                &options.columns,
+            )
+            .map(DataTableSource::Xlsx),
+            #[cfg(not(feature = "xlsx"))]
+            Self::Xlsx => bail!("Unsupported data type: {self}"),
         }
     }
 }
+
+/// How to parse the data files.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableSourceOptions {
+    /// The types of the columns, which are inferred if not given
+    #[serde(default)]
+    pub columns: BTreeMap<String, DataColumnType>,
+
+    /// The field delimiter of the CSV data, such as `;`
+    #[serde(default)]
+    pub delimiter: Option<char>,
+
+    /// The worksheet name of the XLSX data, or the first one if not given
+    #[serde(default)]
+    pub sheet: Option<String>,
+}
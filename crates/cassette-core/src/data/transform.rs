@@ -0,0 +1,541 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
+
+use anyhow::{anyhow, bail, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use strum::{Display, EnumString};
+
+use crate::expr::{is_truthy, ExprContext, ExprScope, Template};
+
+use super::{
+    column::DataColumnType,
+    csv::CsvTable,
+    table::{DataTable, DataTableLog, DataTableLogKind, DataTableSource},
+};
+
+/// Declarative operations on a data table.
+///
+/// The operations are applied in order of:
+/// `filter`, `groupBy`, `sort`, `select`, `rename` and `limit`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableTransform {
+    /// A predicate of each row, such as `~/row/state == 'deployed'`
+    #[serde(default)]
+    pub filter: Option<String>,
+
+    #[serde(default)]
+    pub group_by: Option<DataTableGroupBy>,
+
+    #[serde(default)]
+    pub sort: Vec<DataTableSortKey>,
+
+    /// The columns to keep, in order
+    #[serde(default)]
+    pub select: Option<Vec<String>>,
+
+    /// The new names of the columns
+    #[serde(default)]
+    pub rename: BTreeMap<String, String>,
+
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableGroupBy {
+    pub columns: Vec<String>,
+
+    #[serde(default)]
+    pub aggregate: Vec<DataTableAggregate>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableAggregate {
+    /// The column to aggregate, which is optional for `count`
+    #[serde(default)]
+    pub column: Option<String>,
+
+    pub op: DataTableAggregateOp,
+
+    /// The name of the output column, such as `count` or `sum_size`
+    #[serde(default, rename = "as")]
+    pub name: Option<String>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Display,
+    EnumString,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum DataTableAggregateOp {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableSortKey {
+    pub column: String,
+
+    #[serde(default)]
+    pub order: DataTableSortOrder,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Display,
+    EnumString,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum DataTableSortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl DataTableSortOrder {
+    pub fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            Self::Asc => ordering,
+            Self::Desc => ordering.reverse(),
+        }
+    }
+}
+
+struct Columns {
+    headers: Vec<String>,
+    types: Vec<DataColumnType>,
+}
+
+impl Columns {
+    fn index(&self, name: &str) -> Result<usize> {
+        self.headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| anyhow!("No such column: {name:?}"))
+    }
+}
+
+impl DataTableTransform {
+    /// Apply the operations, resolving the filter with the given context.
+    ///
+    /// The previous output is reused as long as neither the table nor the operations have changed.
+    pub fn apply(
+        &self,
+        ctx: &impl ExprContext,
+        table: &DataTable,
+        previous: Option<&DataTable>,
+    ) -> Result<DataTable> {
+        let log = self.log(ctx, table)?;
+        if let Some(previous) = previous.filter(|previous| previous.log.id == log.id) {
+            return Ok(previous.clone());
+        }
+
+        let mut columns = Columns {
+            headers: table.data.columns()?,
+            types: table.data.column_types()?,
+        };
+        let mut records = table.data.records()?.to_vec();
+
+        if let Some(filter) = &self.filter {
+            let filter: Template = filter
+                .parse()
+                .map_err(|error| anyhow!("Failed to parse filter: {error}"))?;
+
+            let mut filtered = Vec::with_capacity(records.len());
+            for (index, record) in records.into_iter().enumerate() {
+                let ctx = RowContext {
+                    ctx,
+                    vars: row_vars(&columns.headers, index, &record),
+                };
+                match filter.eval(&ctx) {
+                    Ok(value) if is_truthy(&value) => filtered.push(record),
+                    Ok(_) => continue,
+                    Err(error) => bail!("Failed to filter the row #{index}: {error}"),
+                }
+            }
+            records = filtered;
+        }
+
+        if let Some(group_by) = &self.group_by {
+            (columns, records) = group_by.apply(&columns, records)?;
+        }
+
+        if !self.sort.is_empty() {
+            let keys = self
+                .sort
+                .iter()
+                .map(|key| columns.index(&key.column).map(|index| (index, key.order)))
+                .collect::<Result<Vec<_>>>()?;
+
+            records.sort_by(|a, b| {
+                keys.iter()
+                    .map(|(index, order)| order.apply(compare(cell(a, *index), cell(b, *index))))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+
+        if let Some(select) = &self.select {
+            let indices = select
+                .iter()
+                .map(|name| columns.index(name))
+                .collect::<Result<Vec<_>>>()?;
+
+            columns = Columns {
+                headers: indices
+                    .iter()
+                    .map(|&index| columns.headers[index].clone())
+                    .collect(),
+                types: indices.iter().map(|&index| columns.types[index]).collect(),
+            };
+            records = records
+                .into_iter()
+                .map(|record| {
+                    indices
+                        .iter()
+                        .map(|&index| cell(&record, index).clone())
+                        .collect()
+                })
+                .collect();
+        }
+
+        // NOTE: resolve all columns first, so that the names can be swapped such as `{a: b, b: a}`
+        let renames = self
+            .rename
+            .iter()
+            .map(|(from, to)| columns.index(from).map(|index| (index, to)))
+            .collect::<Result<Vec<_>>>()?;
+        for (index, to) in renames {
+            columns.headers[index] = to.clone();
+        }
+
+        if let Some(limit) = self.limit {
+            records.truncate(limit);
+        }
+
+        let Columns { headers, types } = columns;
+        let data = DataTableSource::Csv(CsvTable {
+            headers,
+            types,
+            records: Rc::new(records),
+        });
+        Ok(DataTable {
+            name: table.name.clone(),
+            log: log.follow(previous.map(|previous| &previous.log)),
+            data: Rc::new(data),
+        })
+    }
+
+    /// Derive the log of the output from the input table and the operations.
+    fn log(&self, ctx: &impl ExprContext, table: &DataTable) -> Result<DataTableLog> {
+        // NOTE: the filter may refer to the other tasks, which determine the output as well
+        let references: Vec<_> = match &self.filter {
+            Some(filter) => filter
+                .parse::<Template>()
+                .map_err(|error| anyhow!("Failed to parse filter: {error}"))?
+                .references()
+                .into_iter()
+                .map(|(scope, key)| {
+                    match scope {
+                        ExprScope::Data => ctx.get_data(key),
+                        ExprScope::Spec => ctx.get_spec(key),
+                    }
+                    .unwrap_or_default()
+                })
+                .collect(),
+            None => Vec::default(),
+        };
+
+        DataTableLog::derive(
+            DataTableLogKind::Transformed,
+            [&table.log],
+            &(self, references),
+        )
+    }
+}
+
+impl DataTableGroupBy {
+    fn apply(
+        &self,
+        columns: &Columns,
+        records: Vec<Vec<Value>>,
+    ) -> Result<(Columns, Vec<Vec<Value>>)> {
+        let keys = self
+            .columns
+            .iter()
+            .map(|name| columns.index(name))
+            .collect::<Result<Vec<_>>>()?;
+        let aggregates = self
+            .aggregate
+            .iter()
+            .map(|aggregate| match (&aggregate.column, aggregate.op) {
+                (Some(name), _) => columns.index(name).map(Some),
+                (None, DataTableAggregateOp::Count) => Ok(None),
+                (None, op) => bail!("Aggregate {op} requires a column"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // NOTE: the groups are ordered by their first appearance
+        let mut groups: Vec<(Vec<Value>, Vec<Vec<Value>>)> = Vec::default();
+        let mut group_indices = HashMap::new();
+        for record in records {
+            let key: Vec<_> = keys
+                .iter()
+                .map(|&index| cell(&record, index).clone())
+                .collect();
+            let group_index = *group_indices
+                .entry(Value::Array(key.clone()).to_string())
+                .or_insert_with(|| {
+                    groups.push((key, Vec::default()));
+                    groups.len() - 1
+                });
+            groups[group_index].1.push(record);
+        }
+
+        let mut headers: Vec<_> = keys
+            .iter()
+            .map(|&index| columns.headers[index].clone())
+            .collect();
+        let mut types: Vec<_> = keys.iter().map(|&index| columns.types[index]).collect();
+        for (aggregate, index) in self.aggregate.iter().zip(&aggregates) {
+            let DataTableAggregate { column, op, name } = aggregate;
+            headers.push(name.clone().unwrap_or_else(|| match column {
+                Some(column) => format!("{op}_{column}"),
+                None => op.to_string(),
+            }));
+            types.push(match (op, index) {
+                (DataTableAggregateOp::Count, _) => DataColumnType::Integer,
+                (DataTableAggregateOp::Sum, Some(index))
+                    if columns.types[*index] == DataColumnType::Integer =>
+                {
+                    DataColumnType::Integer
+                }
+                (DataTableAggregateOp::Sum | DataTableAggregateOp::Avg, _) => DataColumnType::Float,
+                (DataTableAggregateOp::Min | DataTableAggregateOp::Max, Some(index)) => {
+                    columns.types[*index]
+                }
+                (DataTableAggregateOp::Min | DataTableAggregateOp::Max, None) => {
+                    DataColumnType::default()
+                }
+            });
+        }
+
+        let records = groups
+            .into_iter()
+            .map(|(mut key, records)| {
+                for (aggregate, index) in self.aggregate.iter().zip(&aggregates) {
+                    let values = records
+                        .iter()
+                        .filter_map(|record| index.map(|index| cell(record, index)));
+                    key.push(match aggregate.op {
+                        DataTableAggregateOp::Count => match index {
+                            Some(_) => values.filter(|value| !value.is_null()).count().into(),
+                            None => records.len().into(),
+                        },
+                        DataTableAggregateOp::Sum => sum(values),
+                        DataTableAggregateOp::Avg => avg(values),
+                        DataTableAggregateOp::Min => values
+                            .min_by(|a, b| compare(a, b))
+                            .cloned()
+                            .unwrap_or_default(),
+                        DataTableAggregateOp::Max => values
+                            .max_by(|a, b| compare(a, b))
+                            .cloned()
+                            .unwrap_or_default(),
+                    });
+                }
+                key
+            })
+            .collect();
+
+        Ok((Columns { headers, types }, records))
+    }
+}
+
+struct RowContext<'a, C> {
+    ctx: &'a C,
+    vars: Value,
+}
+
+impl<C> ExprContext for RowContext<'_, C>
+where
+    C: ExprContext,
+{
+    fn get_data(&self, key: &str) -> Result<Value, String> {
+        self.ctx.get_data(key)
+    }
+
+    fn get_spec(&self, key: &str) -> Result<Value, String> {
+        match self.vars.pointer(key) {
+            Some(value) => Ok(value.clone()),
+            None => self.ctx.get_spec(key),
+        }
+    }
+}
+
+fn row_vars(headers: &[String], index: usize, record: &[Value]) -> Value {
+    let row: Map<_, _> = headers
+        .iter()
+        .cloned()
+        .zip(record.iter().cloned())
+        .collect();
+
+    let mut vars = Map::default();
+    vars.insert("index".into(), index.into());
+    vars.insert("row".into(), Value::Object(row));
+    Value::Object(vars)
+}
+
+pub(super) fn cell(record: &[Value], index: usize) -> &Value {
+    static NULL: Value = Value::Null;
+    record.get(index).unwrap_or(&NULL)
+}
+
+/// Compare the values of a column, putting the nulls first.
+pub fn compare(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => a
+                .as_f64()
+                .unwrap_or_default()
+                .total_cmp(&b.as_f64().unwrap_or_default()),
+        },
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(_), Value::Array(_)) | (Value::Object(_), Value::Object(_)) => {
+            a.to_string().cmp(&b.to_string())
+        }
+        (a, b) => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn sum<'a>(values: impl Iterator<Item = &'a Value>) -> Value {
+    let mut total_i64 = Some(0i64);
+    let mut total_f64 = 0f64;
+    for value in values {
+        if let Value::Number(value) = value {
+            total_i64 = total_i64
+                .zip(value.as_i64())
+                .and_then(|(a, b)| a.checked_add(b));
+            total_f64 += value.as_f64().unwrap_or_default();
+        }
+    }
+
+    match total_i64 {
+        Some(total) => total.into(),
+        None => Number::from_f64(total_f64)
+            .map(Value::Number)
+            .unwrap_or_default(),
+    }
+}
+
+fn avg<'a>(values: impl Iterator<Item = &'a Value>) -> Value {
+    let (total, count) = values
+        .filter_map(Value::as_f64)
+        .fold((0f64, 0usize), |(total, count), value| {
+            (total + value, count + 1)
+        });
+
+    if count == 0 {
+        Value::Null
+    } else {
+        Number::from_f64(total / count as f64)
+            .map(Value::Number)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    struct EmptyContext;
+
+    impl ExprContext for EmptyContext {
+        fn get_data(&self, key: &str) -> Result<Value, String> {
+            Err(format!("No such key: {key}"))
+        }
+
+        fn get_spec(&self, key: &str) -> Result<Value, String> {
+            Err(format!("No such key: {key}"))
+        }
+    }
+
+    #[test]
+    fn rename_swap() {
+        let table = DataTable {
+            name: "sample".into(),
+            data: Rc::new(DataTableSource::Csv(CsvTable {
+                headers: vec!["a".into(), "b".into()],
+                types: vec![DataColumnType::Integer, DataColumnType::String],
+                records: Rc::new(vec![vec![json!(1), json!("x")]]),
+            })),
+            log: DataTableLog::new(DataTableLogKind::Loaded),
+        };
+        let transform = DataTableTransform {
+            rename: [("a".into(), "b".into()), ("b".into(), "a".into())].into(),
+            ..Default::default()
+        };
+
+        let output = transform.apply(&EmptyContext, &table, None).unwrap();
+        assert_eq!(output.data.columns().unwrap(), ["b", "a"]);
+        assert_eq!(
+            output.data.column_types().unwrap(),
+            [DataColumnType::Integer, DataColumnType::String],
+        );
+        assert_eq!(
+            *output.data.records().unwrap(),
+            [vec![json!(1), json!("x")]]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use std::{borrow::Cow, collections::BTreeSet, fmt};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::{Display, EnumString};
+
+use super::transform::{cell, compare, DataTableSortKey};
+
+/// The interactive view of a table, such as the search, the filters,
+/// the sorting and the hidden columns.
+///
+/// The columns which do not exist in the table are ignored,
+/// as the view may outlive its table.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableView {
+    /// The text to search in any visible column, ignoring the case
+    #[serde(default)]
+    pub search: String,
+
+    #[serde(default)]
+    pub filters: Vec<DataTableColumnFilter>,
+
+    #[serde(default)]
+    pub sort: Option<DataTableSortKey>,
+
+    #[serde(default)]
+    pub hidden: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableColumnFilter {
+    pub column: String,
+
+    #[serde(flatten)]
+    pub op: DataTableFilterOp,
+}
+
+impl fmt::Display for DataTableColumnFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { column, op } = self;
+        match op {
+            DataTableFilterOp::Contains { value } => write!(f, "{column} contains {value:?}"),
+            DataTableFilterOp::Equals { value } => write!(f, "{column} = {value:?}"),
+            DataTableFilterOp::Range { min, max } => match (min, max) {
+                (Some(min), Some(max)) => write!(f, "{min} <= {column} <= {max}"),
+                (Some(min), None) => write!(f, "{column} >= {min}"),
+                (None, Some(max)) => write!(f, "{column} <= {max}"),
+                (None, None) => write!(f, "{column} is a number"),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum DataTableFilterOp {
+    /// Match the cells containing the text, ignoring the case
+    Contains { value: String },
+    /// Match the cells which are exactly the text
+    Equals { value: String },
+    /// Match the numeric cells in the inclusive range
+    Range {
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+}
+
+impl DataTableFilterOp {
+    fn test(&self, value: &Value) -> bool {
+        match self {
+            Self::Contains { value: pattern } => cell_text(value)
+                .to_lowercase()
+                .contains(&pattern.to_lowercase()),
+            Self::Equals { value: pattern } => cell_text(value) == pattern.as_str(),
+            Self::Range { min, max } => match cell_number(value) {
+                Some(value) => {
+                    !min.is_some_and(|min| value < min) && !max.is_some_and(|max| max < value)
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Display,
+    EnumString,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum DataTableFilterKind {
+    #[default]
+    Contains,
+    Equals,
+    Range,
+}
+
+impl DataTableFilterKind {
+    pub const ALL: [Self; 3] = [Self::Contains, Self::Equals, Self::Range];
+
+    /// Build a filter from the user input, such as `1..10` for ranges.
+    pub fn parse(&self, value: &str) -> Option<DataTableFilterOp> {
+        match self {
+            Self::Contains => Some(DataTableFilterOp::Contains {
+                value: value.into(),
+            }),
+            Self::Equals => Some(DataTableFilterOp::Equals {
+                value: value.into(),
+            }),
+            Self::Range => {
+                let parse = |value: &str| match value.trim() {
+                    "" => Some(None),
+                    value => value.parse().ok().map(Some),
+                };
+                let (min, max) = match value.split_once("..") {
+                    Some((min, max)) => (parse(min)?, parse(max)?),
+                    None => {
+                        let value = parse(value)?;
+                        (value, value)
+                    }
+                };
+                Some(DataTableFilterOp::Range { min, max })
+            }
+        }
+    }
+}
+
+impl DataTableView {
+    /// Get the indices of the visible columns.
+    pub fn columns(&self, headers: &[String]) -> Vec<usize> {
+        headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| !self.hidden.contains(*header))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Get the indices of the matched records, in the sorted order.
+    pub fn rows(&self, headers: &[String], records: &[Vec<Value>]) -> Vec<usize> {
+        let position = |column: &str| headers.iter().position(|header| header == column);
+
+        let filters: Vec<_> = self
+            .filters
+            .iter()
+            .filter_map(|filter| Some((position(&filter.column)?, &filter.op)))
+            .collect();
+        let search = self.search.trim().to_lowercase();
+        let columns = self.columns(headers);
+
+        let mut rows: Vec<_> = records
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| {
+                filters
+                    .iter()
+                    .all(|(index, op)| op.test(cell(record, *index)))
+            })
+            .filter(|(_, record)| {
+                search.is_empty()
+                    || columns.iter().any(|index| {
+                        cell_text(cell(record, *index))
+                            .to_lowercase()
+                            .contains(&search)
+                    })
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        if let Some(sort) = &self.sort {
+            if let Some(index) = position(&sort.column) {
+                rows.sort_by(|&a, &b| {
+                    sort.order
+                        .apply(compare(cell(&records[a], index), cell(&records[b], index)))
+                });
+            }
+        }
+        rows
+    }
+}
+
+/// Get the text of the cell as displayed.
+pub fn cell_text(value: &Value) -> Cow<'_, str> {
+    match value {
+        Value::Null => Cow::Borrowed(""),
+        Value::String(value) => Cow::Borrowed(value),
+        value => Cow::Owned(value.to_string()),
+    }
+}
+
+/// Get the number of the cell, including the numeric strings.
+pub fn cell_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(value) => value.as_f64(),
+        Value::String(value) => value.trim().parse().ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,139 @@
+use std::{collections::BTreeMap, io::Cursor, rc::Rc};
+
+use anyhow::{anyhow, Result};
+use calamine::{open_workbook_from_rs, Data, Reader, Xlsx};
+use rust_xlsxwriter::Workbook;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+use super::column::DataColumnType;
+
+/// A worksheet of the Excel workbook, whose first row is the headers.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct XlsxTable {
+    pub sheet: String,
+    pub headers: Vec<String>,
+    #[serde(default)]
+    pub types: Vec<DataColumnType>,
+    pub records: Rc<Vec<Vec<Value>>>,
+}
+
+impl XlsxTable {
+    pub(super) fn from_bytes(
+        bytes: Vec<u8>,
+        sheet: Option<&str>,
+        overrides: &BTreeMap<String, DataColumnType>,
+    ) -> Result<Self> {
+        let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes))?;
+
+        let sheet = match sheet {
+            Some(sheet) => sheet.to_string(),
+            None => workbook
+                .sheet_names()
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("Empty workbook"))?,
+        };
+        let range = workbook.worksheet_range(&sheet)?;
+
+        let mut rows = range.rows();
+        let headers: Vec<String> = rows
+            .next()
+            .map(|row| row.iter().map(ToString::to_string).collect())
+            .unwrap_or_default();
+        let mut records = rows
+            .map(|row| row.iter().map(parse_cell).collect())
+            .collect::<Vec<_>>();
+        let types = super::column::infer_types(&headers, &mut records, overrides);
+
+        Ok(Self {
+            sheet,
+            headers,
+            types,
+            records: Rc::new(records),
+        })
+    }
+
+    pub fn columns(&self) -> Vec<String> {
+        self.headers.clone()
+    }
+
+    pub fn column_types(&self) -> Vec<DataColumnType> {
+        self.types.clone()
+    }
+
+    pub fn first_row(&self) -> Option<Vec<Value>> {
+        self.records.first().cloned()
+    }
+
+    pub fn records(&self) -> Rc<Vec<Vec<Value>>> {
+        self.records.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+}
+
+/// Write the records into a new workbook with a single worksheet.
+pub(super) fn to_bytes(sheet: &str, headers: &[String], records: &[Vec<Value>]) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // NOTE: Excel forbids some characters and long names for the worksheets
+    let sheet: String = sheet
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .take(31)
+        .collect();
+    if !sheet.is_empty() {
+        worksheet.set_name(sheet)?;
+    }
+
+    for (col, header) in headers.iter().enumerate() {
+        worksheet.write_string(0, col.try_into()?, header)?;
+    }
+    for (row, record) in records.iter().enumerate() {
+        let row = (row + 1).try_into()?;
+        for (col, value) in record.iter().enumerate() {
+            let col = col.try_into()?;
+            match value {
+                Value::Null => continue,
+                Value::Bool(value) => worksheet.write_boolean(row, col, *value)?,
+                Value::Number(value) => {
+                    worksheet.write_number(row, col, value.as_f64().unwrap_or_default())?
+                }
+                Value::String(value) => worksheet.write_string(row, col, value)?,
+                value => worksheet.write_string(row, col, value.to_string())?,
+            };
+        }
+    }
+    workbook.save_to_buffer().map_err(Into::into)
+}
+
+fn parse_cell(cell: &Data) -> Value {
+    match cell {
+        Data::Int(value) => Value::Number((*value).into()),
+        Data::Float(value) => Number::from_f64(*value)
+            .map(Value::Number)
+            .unwrap_or_default(),
+        Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => {
+            Value::String(value.clone())
+        }
+        Data::Bool(value) => Value::Bool(*value),
+        Data::DateTime(value) => value
+            .as_datetime()
+            .map(|value| Value::String(value.and_utc().to_rfc3339()))
+            .unwrap_or_default(),
+        Data::Error(error) => Value::String(error.to_string()),
+        Data::Empty => Value::Null,
+    }
+}
@@ -0,0 +1,83 @@
+use serde_json::Value;
+
+pub(super) fn apply(name: &str, value: Value, args: Vec<Value>) -> Result<Value, String> {
+    let mut args = args.into_iter();
+    let mut next_arg = || args.next().unwrap_or(Value::Null);
+
+    match name {
+        "default" => match value {
+            Value::Null => Ok(next_arg()),
+            value => Ok(value),
+        },
+        "first" => match value {
+            Value::Array(values) => Ok(values.into_iter().next().unwrap_or_default()),
+            Value::String(value) => Ok(value
+                .chars()
+                .next()
+                .map(|c| Value::String(c.into()))
+                .unwrap_or_default()),
+            value => Err(invalid_type(name, &value)),
+        },
+        "join" => match value {
+            Value::Array(values) => {
+                let sep = super::to_string(&next_arg());
+                Ok(Value::String(
+                    values
+                        .iter()
+                        .map(super::to_string)
+                        .collect::<Vec<_>>()
+                        .join(&sep),
+                ))
+            }
+            value => Err(invalid_type(name, &value)),
+        },
+        "json" => ::serde_json::to_string(&value)
+            .map(Value::String)
+            .map_err(|error| format!("Failed to encode value as json: {error}")),
+        "keys" => match value {
+            Value::Object(map) => Ok(Value::Array(
+                map.into_iter().map(|(key, _)| key.into()).collect(),
+            )),
+            value => Err(invalid_type(name, &value)),
+        },
+        "last" => match value {
+            Value::Array(values) => Ok(values.into_iter().last().unwrap_or_default()),
+            Value::String(value) => Ok(value
+                .chars()
+                .last()
+                .map(|c| Value::String(c.into()))
+                .unwrap_or_default()),
+            value => Err(invalid_type(name, &value)),
+        },
+        "length" => match value {
+            Value::Null => Ok(0.into()),
+            Value::Array(values) => Ok(values.len().into()),
+            Value::Object(map) => Ok(map.len().into()),
+            Value::String(value) => Ok(value.chars().count().into()),
+            value => Err(invalid_type(name, &value)),
+        },
+        "lower" => Ok(Value::String(super::to_string(&value).to_lowercase())),
+        "string" => Ok(Value::String(super::to_string(&value))),
+        "trim" => Ok(Value::String(super::to_string(&value).trim().into())),
+        "upper" => Ok(Value::String(super::to_string(&value).to_uppercase())),
+        "values" => match value {
+            Value::Object(map) => Ok(Value::Array(
+                map.into_iter().map(|(_, value)| value).collect(),
+            )),
+            value => Err(invalid_type(name, &value)),
+        },
+        name => Err(format!("Unknown filter: {name}")),
+    }
+}
+
+fn invalid_type(name: &str, value: &Value) -> String {
+    let ty = match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+    format!("Filter {name:?} cannot be applied to {ty}")
+}
@@ -0,0 +1,442 @@
+mod filter;
+mod parser;
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use serde_json::Value;
+
+pub trait ExprContext {
+    /// Resolve a cassette-wide reference, such as `:/name/text`.
+    fn get_data(&self, key: &str) -> Result<Value, String>;
+
+    /// Resolve a task-local reference, such as `~/default`.
+    fn get_spec(&self, key: &str) -> Result<Value, String>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    Pointer {
+        scope: ExprScope,
+        key: String,
+    },
+    Not(Box<Self>),
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Self>,
+        rhs: Box<Self>,
+    },
+    Filter {
+        target: Box<Self>,
+        name: String,
+        args: Vec<Self>,
+    },
+}
+
+impl FromStr for Expr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        self::parser::Parser::parse_all(s)
+    }
+}
+
+impl Expr {
+    pub fn eval(&self, ctx: &impl ExprContext) -> Result<Value, String> {
+        match self {
+            Self::Literal(value) => Ok(value.clone()),
+            Self::Pointer {
+                scope: ExprScope::Data,
+                key,
+            } => ctx.get_data(key),
+            Self::Pointer {
+                scope: ExprScope::Spec,
+                key,
+            } => ctx.get_spec(key),
+            Self::Not(expr) => expr.eval(ctx).map(|value| Value::Bool(!is_truthy(&value))),
+            Self::Binary { op, lhs, rhs } => match op {
+                BinaryOp::And => Ok(Value::Bool(
+                    is_truthy(&lhs.eval(ctx)?) && is_truthy(&rhs.eval(ctx)?),
+                )),
+                BinaryOp::Or => Ok(Value::Bool(
+                    is_truthy(&lhs.eval(ctx)?) || is_truthy(&rhs.eval(ctx)?),
+                )),
+                // NOTE: missing keys are treated as null, so that fallbacks can be chained
+                BinaryOp::Coalesce => match lhs.eval(ctx) {
+                    Ok(Value::Null) | Err(_) => rhs.eval(ctx),
+                    Ok(value) => Ok(value),
+                },
+                op => {
+                    let lhs = lhs.eval(ctx)?;
+                    let rhs = rhs.eval(ctx)?;
+                    Ok(Value::Bool(op.compare(&lhs, &rhs)))
+                }
+            },
+            Self::Filter { target, name, args } => {
+                let value = target.eval(ctx)?;
+                let args = args
+                    .iter()
+                    .map(|arg| arg.eval(ctx))
+                    .collect::<Result<_, _>>()?;
+                self::filter::apply(name, value, args)
+            }
+        }
+    }
+
+    pub fn eval_bool(&self, ctx: &impl ExprContext) -> Result<bool, String> {
+        self.eval(ctx).map(|value| is_truthy(&value))
+    }
+
+    /// Collect all references in order of appearance.
+    pub fn references(&self) -> Vec<(ExprScope, &str)> {
+        fn collect<'a>(expr: &'a Expr, references: &mut Vec<(ExprScope, &'a str)>) {
+            match expr {
+                Expr::Literal(_) => (),
+                Expr::Pointer { scope, key } => references.push((*scope, key)),
+                Expr::Not(expr) => collect(expr, references),
+                Expr::Binary { op: _, lhs, rhs } => {
+                    collect(lhs, references);
+                    collect(rhs, references);
+                }
+                Expr::Filter {
+                    target,
+                    name: _,
+                    args,
+                } => {
+                    collect(target, references);
+                    args.iter().for_each(|arg| collect(arg, references));
+                }
+            }
+        }
+
+        let mut references = Vec::default();
+        collect(self, &mut references);
+        references
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExprScope {
+    /// `:/`: the states of the cassette tasks
+    Data,
+    /// `~/`: the spec of the current task
+    Spec,
+}
+
+impl ExprScope {
+    const fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix {
+            ':' => Some(Self::Data),
+            '~' => Some(Self::Spec),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    And,
+    Or,
+    Coalesce,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::And => "&&".fmt(f),
+            Self::Or => "||".fmt(f),
+            Self::Coalesce => "??".fmt(f),
+            Self::Eq => "==".fmt(f),
+            Self::Ne => "!=".fmt(f),
+            Self::Lt => "<".fmt(f),
+            Self::Le => "<=".fmt(f),
+            Self::Gt => ">".fmt(f),
+            Self::Ge => ">=".fmt(f),
+        }
+    }
+}
+
+impl BinaryOp {
+    fn compare(&self, lhs: &Value, rhs: &Value) -> bool {
+        let ordering = compare(lhs, rhs);
+        match self {
+            Self::Eq => ordering == Some(Ordering::Equal) || lhs == rhs,
+            Self::Ne => !(ordering == Some(Ordering::Equal) || lhs == rhs),
+            Self::Lt => ordering == Some(Ordering::Less),
+            Self::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Self::Gt => ordering == Some(Ordering::Greater),
+            Self::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            Self::And | Self::Or | Self::Coalesce => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Template {
+    /// A plain string without any expressions
+    Literal(String),
+    /// A whole value, such as `:/name/text` or `${:/a ?? 'x'}`
+    Expr(Expr),
+    /// A string with embedded expressions, such as `Hello ${:/name/text}`
+    Interpolated(Vec<TemplatePart>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplatePart {
+    Text(String),
+    Expr(Expr),
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("\\:/") || s.starts_with("\\~/") {
+            return Ok(Self::Literal(s[1..].into()));
+        }
+        let mut chars = s.chars();
+        if let (Some(prefix), Some('/')) = (chars.next(), chars.next()) {
+            if let Some(scope) = ExprScope::from_prefix(prefix) {
+                // NOTE: a bare reference which is not an expression is a pointer as a whole,
+                //       so that it may contain whitespaces or delimiters, such as `:/table/First Name`
+                return Ok(Self::Expr(s.parse::<Expr>().unwrap_or_else(|_| {
+                    Expr::Pointer {
+                        scope,
+                        key: s[1..].into(),
+                    }
+                })));
+            }
+        }
+        if !s.contains("${") {
+            return Ok(Self::Literal(s.into()));
+        }
+
+        let mut parts = Vec::default();
+        let mut text = String::default();
+        let mut rest = s;
+        while let Some(index) = rest.find("${") {
+            // escaped: `$${`
+            if rest[..index].ends_with('$') {
+                text.push_str(&rest[..index - 1]);
+                text.push_str("${");
+                rest = &rest[index + 2..];
+                continue;
+            }
+
+            text.push_str(&rest[..index]);
+            if !text.is_empty() {
+                parts.push(TemplatePart::Text(::core::mem::take(&mut text)));
+            }
+
+            let (expr, len) = self::parser::Parser::parse_template(&rest[index + 2..])?;
+            parts.push(TemplatePart::Expr(expr));
+            rest = &rest[index + 2 + len..];
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+
+        match parts.len() {
+            0 => Ok(Self::Literal(String::default())),
+            1 => match parts.pop().unwrap() {
+                TemplatePart::Text(text) => Ok(Self::Literal(text)),
+                TemplatePart::Expr(expr) => Ok(Self::Expr(expr)),
+            },
+            _ => Ok(Self::Interpolated(parts)),
+        }
+    }
+}
+
+impl Template {
+    pub fn eval(&self, ctx: &impl ExprContext) -> Result<Value, String> {
+        match self {
+            Self::Literal(text) => Ok(Value::String(text.clone())),
+            Self::Expr(expr) => expr.eval(ctx),
+            Self::Interpolated(parts) => parts
+                .iter()
+                .map(|part| match part {
+                    TemplatePart::Text(text) => Ok(text.clone()),
+                    TemplatePart::Expr(expr) => expr.eval(ctx).map(|value| to_string(&value)),
+                })
+                .collect::<Result<String, _>>()
+                .map(Value::String),
+        }
+    }
+
+    pub const fn is_dynamic(&self) -> bool {
+        !matches!(self, Self::Literal(_))
+    }
+
+    /// Collect all references in order of appearance.
+    pub fn references(&self) -> Vec<(ExprScope, &str)> {
+        match self {
+            Self::Literal(_) => Vec::default(),
+            Self::Expr(expr) => expr.references(),
+            Self::Interpolated(parts) => parts
+                .iter()
+                .flat_map(|part| match part {
+                    TemplatePart::Text(_) => Vec::default(),
+                    TemplatePart::Expr(expr) => expr.references(),
+                })
+                .collect(),
+        }
+    }
+}
+
+pub fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(value) => value.as_f64().map(|value| value != 0.0).unwrap_or(true),
+        Value::String(value) => !value.is_empty(),
+        Value::Array(value) => !value.is_empty(),
+        Value::Object(value) => !value.is_empty(),
+    }
+}
+
+pub fn to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::default(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (Value::Bool(lhs), Value::Bool(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Number(lhs), Value::Number(rhs)) => lhs.as_f64()?.partial_cmp(&rhs.as_f64()?),
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    struct Context(Value);
+
+    impl ExprContext for Context {
+        fn get_data(&self, key: &str) -> Result<Value, String> {
+            self.0
+                .pointer(key)
+                .cloned()
+                .ok_or_else(|| format!("No such key: {key}"))
+        }
+
+        fn get_spec(&self, key: &str) -> Result<Value, String> {
+            self.get_data(key)
+        }
+    }
+
+    fn eval(src: &str) -> Result<Value, String> {
+        let ctx = Context(json!({
+            "name": { "text": "  Alice  " },
+            "empty": null,
+            "items": [1, 2, 3],
+            "table": { "First Name": "Bob" },
+            "query": {
+                "a?b": 1,
+                "x=y": 2,
+                "really!": 3,
+                "my-key": 4,
+                "a&b": 5,
+            },
+        }));
+        src.parse::<Template>()?.eval(&ctx)
+    }
+
+    #[test]
+    fn filter_applies_to_coalesce() {
+        assert_eq!(eval("${:/empty ?? ' World ' | trim}"), Ok(json!("World")));
+        assert_eq!(eval("${:/name/text ?? 'x' | trim}"), Ok(json!("Alice")));
+        assert_eq!(eval("${:/empty | default('x') ?? 'y'}"), Ok(json!("x")));
+        assert_eq!(eval("${:/missing ?? (' y ' | trim)}"), Ok(json!("y")));
+    }
+
+    #[test]
+    fn filter_binds_tighter_than_compare() {
+        assert_eq!(eval("${:/items | length > 2}"), Ok(json!(true)));
+        assert_eq!(
+            eval("${:/items | length == 3 && !:/empty}"),
+            Ok(json!(true))
+        );
+        assert_eq!(eval("${:/empty ?? 0 < 1 || false}"), Ok(json!(true)));
+    }
+
+    #[test]
+    fn filters() {
+        assert_eq!(eval(":/name/text | trim | upper"), Ok(json!("ALICE")));
+        assert_eq!(eval(":/items | first"), Ok(json!(1)));
+        assert_eq!(eval(":/items | last"), Ok(json!(3)));
+        assert_eq!(eval(":/items | join('-')"), Ok(json!("1-2-3")));
+        assert_eq!(eval(":/items | json"), Ok(json!("[1,2,3]")));
+        assert_eq!(eval(":/table | keys"), Ok(json!(["First Name"])));
+        assert!(eval(":/items | unknown").is_err());
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(eval("${-1.5e+2 < 0}"), Ok(json!(true)));
+        assert_eq!(eval("${2 >= 1}"), Ok(json!(true)));
+        // NOTE: `-` is not a part of the number `1`
+        assert!("1-2"
+            .parse::<Expr>()
+            .unwrap_err()
+            .starts_with("unexpected token at position 1"));
+    }
+
+    #[test]
+    fn bare_pointer_with_whitespaces() {
+        assert_eq!(eval(":/table/First Name"), Ok(json!("Bob")));
+        assert_eq!(
+            eval("Hello ${:/name/text | trim}!"),
+            Ok(json!("Hello Alice!"))
+        );
+    }
+
+    #[test]
+    fn bare_pointer_with_delimiters() {
+        // NOTE: the bare references which are not expressions are pointers as a whole
+        assert_eq!(eval(":/query/a?b"), Ok(json!(1)));
+        assert_eq!(eval(":/query/x=y"), Ok(json!(2)));
+        assert_eq!(eval(":/query/really!"), Ok(json!(3)));
+        assert_eq!(eval(":/query/a&b"), Ok(json!(5)));
+        assert_eq!(eval("~/query/a?b"), Ok(json!(1)));
+
+        // NOTE: `-` is not a delimiter, even in the expressions
+        assert_eq!(eval(":/query/my-key"), Ok(json!(4)));
+        assert_eq!(eval("${:/query/my-key}"), Ok(json!(4)));
+        assert_eq!(eval(":/query/my-key == 4"), Ok(json!(true)));
+
+        // NOTE: the pointers in the expressions end at the delimiters
+        assert_eq!(eval(":/query/a ?? 'x'"), Ok(json!("x")));
+        assert_eq!(eval(":/empty ?? 'x'"), Ok(json!("x")));
+        assert!("${:/query/a?b}".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn escaped_templates() {
+        assert_eq!(eval("$${:/name/text}"), Ok(json!("${:/name/text}")));
+        assert_eq!(
+            eval("echo $${HOME} ${:/name/text | trim}"),
+            Ok(json!("echo ${HOME} Alice")),
+        );
+        assert_eq!(eval("$5 and $$5"), Ok(json!("$5 and $$5")));
+        assert!("echo ${HOME}".parse::<Template>().is_err());
+
+        assert_eq!(eval("\\:/name/text"), Ok(json!(":/name/text")));
+        assert_eq!(eval("\\~/name/text"), Ok(json!("~/name/text")));
+    }
+}
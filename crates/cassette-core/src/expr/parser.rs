@@ -0,0 +1,288 @@
+use serde_json::{Number, Value};
+
+use super::{BinaryOp, Expr, ExprScope};
+
+pub(super) struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    const fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    pub(super) fn parse_all(src: &'a str) -> Result<Expr, String> {
+        let mut parser = Self::new(src);
+        let expr = parser.parse_expr()?;
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(expr),
+            Some(_) => Err(parser.error("unexpected token")),
+        }
+    }
+
+    /// Parse an expression terminated by `}`, returning the consumed length.
+    pub(super) fn parse_template(src: &'a str) -> Result<(Expr, usize), String> {
+        let mut parser = Self::new(src);
+        let expr = parser.parse_expr()?;
+
+        parser.skip_whitespace();
+        if parser.eat("}") {
+            Ok((expr, parser.pos))
+        } else {
+            Err(parser.error("expected '}'"))
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while self.eat_op("||") {
+            let rhs = self.parse_and()?;
+            lhs = binary(BinaryOp::Or, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_compare()?;
+        while self.eat_op("&&") {
+            let rhs = self.parse_compare()?;
+            lhs = binary(BinaryOp::And, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_compare(&mut self) -> Result<Expr, String> {
+        let lhs = self.parse_pipe()?;
+
+        // NOTE: longer operators first
+        let op = [
+            ("==", BinaryOp::Eq),
+            ("!=", BinaryOp::Ne),
+            ("<=", BinaryOp::Le),
+            (">=", BinaryOp::Ge),
+            ("<", BinaryOp::Lt),
+            (">", BinaryOp::Gt),
+        ]
+        .into_iter()
+        .find(|(token, _)| self.eat_op(token))
+        .map(|(_, op)| op);
+
+        match op {
+            Some(op) => {
+                let rhs = self.parse_pipe()?;
+                Ok(binary(op, lhs, rhs))
+            }
+            None => Ok(lhs),
+        }
+    }
+
+    /// Parse the fallbacks (`??`) and the filters (`|`), which are applied from left to right.
+    ///
+    /// i.e. `:/a ?? 'x' | trim` trims either of them.
+    fn parse_pipe(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            if self.eat_op("??") {
+                let rhs = self.parse_unary()?;
+                lhs = binary(BinaryOp::Coalesce, lhs, rhs);
+                continue;
+            }
+
+            self.skip_whitespace();
+            if !self.rest().starts_with('|') || self.rest().starts_with("||") {
+                break Ok(lhs);
+            }
+            self.pos += 1;
+
+            self.skip_whitespace();
+            let name = self
+                .parse_ident()
+                .ok_or_else(|| self.error("expected a filter name"))?;
+
+            let mut args = Vec::default();
+            if self.eat_op("(") {
+                if !self.eat_op(")") {
+                    loop {
+                        args.push(self.parse_expr()?);
+                        if self.eat_op(")") {
+                            break;
+                        }
+                        if !self.eat_op(",") {
+                            return Err(self.error("expected ',' or ')'"));
+                        }
+                    }
+                }
+            }
+
+            lhs = Expr::Filter {
+                target: Box::new(lhs),
+                name,
+                args,
+            };
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+        if self.rest().starts_with('!') && !self.rest().starts_with("!=") {
+            self.pos += 1;
+            return self.parse_unary().map(Box::new).map(Expr::Not);
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+
+        let mut chars = self.rest().chars();
+        match (chars.next(), chars.next()) {
+            (Some('('), _) => {
+                self.pos += 1;
+                let expr = self.parse_expr()?;
+                if self.eat_op(")") {
+                    Ok(expr)
+                } else {
+                    Err(self.error("expected ')'"))
+                }
+            }
+            (Some(prefix @ (':' | '~')), Some('/')) => {
+                self.pos += 1;
+                let key = self.take_while(|c| !c.is_whitespace() && !is_delimiter(c));
+                Ok(Expr::Pointer {
+                    scope: ExprScope::from_prefix(prefix).unwrap(),
+                    key: key.into(),
+                })
+            }
+            (Some(quote @ ('\'' | '"')), _) => self.parse_string(quote),
+            (Some('0'..='9'), _) | (Some('-'), Some('0'..='9')) => self.parse_number(),
+            (Some(c), _) if c.is_alphabetic() || c == '_' => {
+                match self.parse_ident().unwrap_or_default().as_str() {
+                    "true" => Ok(Expr::Literal(Value::Bool(true))),
+                    "false" => Ok(Expr::Literal(Value::Bool(false))),
+                    "null" => Ok(Expr::Literal(Value::Null)),
+                    ident => Err(self.error(&format!("unknown identifier {ident:?}"))),
+                }
+            }
+            (Some(_), _) => Err(self.error("unexpected token")),
+            (None, _) => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    fn parse_ident(&mut self) -> Option<String> {
+        let ident = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        if ident.is_empty() {
+            None
+        } else {
+            Some(ident.into())
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Expr, String> {
+        let start = self.pos;
+
+        // NOTE: the signs are taken only at the beginning and after the exponent,
+        // so that `1-2` is not taken as a number
+        self.eat("-");
+        self.take_while(|c| c.is_ascii_digit());
+        if self.rest().starts_with('.') {
+            self.pos += 1;
+            self.take_while(|c| c.is_ascii_digit());
+        }
+        if self.rest().starts_with(['e', 'E']) {
+            self.pos += 1;
+            if !self.eat("+") {
+                self.eat("-");
+            }
+            self.take_while(|c| c.is_ascii_digit());
+        }
+
+        let text = &self.src[start..self.pos];
+        text.parse::<Number>()
+            .map(Value::Number)
+            .map(Expr::Literal)
+            .map_err(|_| self.error(&format!("invalid number {text:?}")))
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<Expr, String> {
+        self.pos += 1;
+
+        let mut value = String::default();
+        let mut chars = self.rest().char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, c)) => value.push(c),
+                    None => break,
+                },
+                c if c == quote => {
+                    self.pos += index + 1;
+                    return Ok(Expr::Literal(Value::String(value)));
+                }
+                c => value.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_op(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        self.eat(token)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn error(&self, msg: &str) -> String {
+        format!("{msg} at position {}: {:?}", self.pos, self.src)
+    }
+}
+
+fn binary(op: BinaryOp, lhs: Expr, rhs: Expr) -> Expr {
+    Expr::Binary {
+        op,
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+    }
+}
+
+/// Check whether the character ends a pointer in an expression, such as `?` of `:/a ?? 'x'`.
+const fn is_delimiter(c: char) -> bool {
+    matches!(
+        c,
+        '(' | ')' | '|' | ',' | '?' | '!' | '<' | '>' | '=' | '&' | '\'' | '"' | '}'
+    )
+}
@@ -2,9 +2,12 @@ pub mod cassette;
 pub mod components;
 pub mod data;
 pub mod document;
+pub mod expr;
 #[cfg(feature = "ui")]
 pub mod keycode;
 pub mod net;
+#[cfg(feature = "ui")]
+mod persist;
 pub mod result;
 pub mod task;
 
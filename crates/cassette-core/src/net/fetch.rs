@@ -155,6 +155,70 @@ impl<Uri, Req> FetchRequest<Uri, Req> {
     }
 }
 
+impl<Uri, Req> FetchRequest<Uri, Req> {
+    /// Fetch the raw response body, such as a data file, and parse it with the given handler.
+    pub fn try_fetch_bytes<State, Res, F>(self, base_url: &str, state: State, handler: F)
+    where
+        State: 'static + GenericCassetteTaskHandle<FetchState<Res>>,
+        for<'a> <State as GenericCassetteTaskHandle<FetchState<Res>>>::Ref<'a>:
+            ops::Deref<Target = FetchState<Res>>,
+        Req: 'static + Serialize,
+        Res: 'static,
+        Uri: fmt::Display,
+        F: 'static + FnOnce(Vec<u8>) -> FetchState<Res>,
+    {
+        if matches!(*state.get(), FetchState::Pending) {
+            state.set(FetchState::Fetching);
+
+            let Self {
+                method,
+                name,
+                uri,
+                body,
+            } = self;
+            let url = format!("{base_url}{uri}");
+
+            let state = state.clone();
+            spawn_local(async move {
+                let builder = RequestBuilder::new(&url).method(method);
+                let builder = match body {
+                    Some(Body::Json(body)) => builder.json(&body).map_err(|error| {
+                        FetchState::Error(format!("Failed to encode the body {name}: {error}"))
+                    }),
+                    None => builder.build().map_err(|error| {
+                        FetchState::Error(format!("Failed to build the request {name}: {error}"))
+                    }),
+                };
+
+                let value = match builder {
+                    Ok(builder) => match builder.send().await {
+                        Ok(response) if !response.ok() => match response.text().await {
+                            Ok(text) if !text.is_empty() => FetchState::Error(text),
+                            _ => FetchState::Error(format!(
+                                "Failed to fetch the {name}: {}",
+                                response.status_text(),
+                            )),
+                        },
+                        Ok(response) => match response.binary().await {
+                            Ok(bytes) => handler(bytes),
+                            Err(error) => {
+                                FetchState::Error(format!("Failed to read the {name}: {error}"))
+                            }
+                        },
+                        Err(error) => {
+                            FetchState::Error(format!("Failed to fetch the {name}: {error}"))
+                        }
+                    },
+                    Err(state) => state,
+                };
+                if matches!(*state.get(), FetchState::Pending | FetchState::Fetching) {
+                    state.set(value);
+                }
+            })
+        }
+    }
+}
+
 impl<Uri, Req> FetchRequest<Uri, Req> {
     pub fn try_stream_with<'reader, State, Res, F, Fut>(
         self,
@@ -0,0 +1,120 @@
+use gloo_storage::{LocalStorage, Storage as _};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use uuid::Uuid;
+use wasm_bindgen::JsValue;
+
+use crate::task::CassetteTaskMetadata;
+
+/// Where to keep a handler value of a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PersistKey {
+    /// The local storage key, such as `cassette/<id>/name/text`
+    storage: Option<String>,
+    /// The URL query key, such as `name.text`
+    query: Option<String>,
+}
+
+impl PersistKey {
+    pub(crate) fn new(
+        id: Uuid,
+        metadata: &CassetteTaskMetadata,
+        task_path: &str,
+        handler_name: &str,
+    ) -> Option<Self> {
+        let storage = if metadata.persist {
+            Some(format!("cassette/{id}{task_path}/{handler_name}"))
+        } else {
+            None
+        };
+        let query = if metadata.query {
+            Some(format!(
+                "{}.{handler_name}",
+                task_path.trim_start_matches('/').replace('/', "."),
+            ))
+        } else {
+            None
+        };
+
+        if storage.is_some() || query.is_some() {
+            Some(Self { storage, query })
+        } else {
+            None
+        }
+    }
+
+    /// Load the value, preferring the URL query to the local storage.
+    pub(crate) fn load<T>(&self) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.query
+            .as_deref()
+            .and_then(load_query)
+            .and_then(|value| decode_query(&value))
+            .or_else(|| {
+                self.storage
+                    .as_deref()
+                    .and_then(|key| LocalStorage::raw().get_item(key).ok().flatten())
+                    .and_then(|value| ::serde_json::from_str(&value).ok())
+            })
+    }
+
+    pub(crate) fn store(&self, value: &Value) {
+        if let Some(key) = self.storage.as_deref() {
+            let storage = LocalStorage::raw();
+            match value {
+                Value::Null => storage.remove_item(key).ok(),
+                value => ::serde_json::to_string(value)
+                    .ok()
+                    .and_then(|value| storage.set_item(key, &value).ok()),
+            };
+        }
+        if let Some(key) = self.query.as_deref() {
+            store_query(key, value);
+        }
+    }
+}
+
+fn load_query(key: &str) -> Option<String> {
+    let search = ::web_sys::window()?.location().search().ok()?;
+    ::web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(key)
+}
+
+fn store_query(key: &str, value: &Value) -> Option<()> {
+    let window = ::web_sys::window()?;
+    let url = ::web_sys::Url::new(&window.location().href().ok()?).ok()?;
+
+    let params = url.search_params();
+    match encode_query(value) {
+        Some(value) => params.set(key, &value),
+        None => params.delete(key),
+    }
+
+    window
+        .history()
+        .ok()?
+        .replace_state_with_url(&JsValue::NULL, "", Some(&url.href()))
+        .ok()
+}
+
+fn decode_query<T>(value: &str) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    // NOTE: plain strings do not need to be quoted
+    ::serde_json::from_str(value)
+        .ok()
+        .or_else(|| ::serde_json::from_value(Value::String(value.into())).ok())
+}
+
+fn encode_query(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) if value.is_empty() => None,
+        Value::String(value) => Some(value.clone()),
+        value => ::serde_json::to_string(value).ok(),
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use garde::Validate;
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -32,6 +34,14 @@ pub struct CassetteTask<Spec = TaskSpec> {
 pub struct CassetteTaskMetadata {
     #[serde(default)]
     pub column: CassetteTaskColumnType,
+    #[serde(default)]
+    pub when: Option<String>,
+    /// Keep the handler values in the local storage, across page reloads
+    #[serde(default)]
+    pub persist: bool,
+    /// Prefill the handler values from the URL query, and keep them up to date
+    #[serde(default)]
+    pub query: bool,
 }
 
 #[derive(
@@ -109,7 +119,7 @@ impl Default for TaskSpec {
 }
 
 impl TaskSpec {
-    fn preserve_arbitrary(
+    pub(crate) fn preserve_arbitrary(
         _gen: &mut ::schemars::gen::SchemaGenerator,
     ) -> ::schemars::schema::Schema {
         let mut obj = ::schemars::schema::SchemaObject::default();
@@ -141,6 +151,20 @@ impl TaskSpec {
         }
     }
 
+    pub(crate) fn get_at(&self, path: &TaskPath) -> Option<&Value> {
+        path.get(&self.0)
+    }
+
+    pub(crate) fn set(&mut self, path: &TaskPath, value: Self) -> bool {
+        let value = value.0;
+        if path.get(&self.0) != Some(&value) {
+            path.set(&mut self.0, value);
+            true
+        } else {
+            false
+        }
+    }
+
     pub(crate) fn set_child(&mut self, name: &str, value: Self) -> bool {
         let value = value.0;
         match &mut self.0 {
@@ -171,3 +195,82 @@ impl TaskSpec {
         }
     }
 }
+
+/// The location of a task state, which can be nested in the other tasks, such as `ForEach`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskPath(Vec<TaskPathItem>);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum TaskPathItem {
+    /// An item of an array, such as the states of an iteration
+    Index(usize),
+    /// A field of an object, such as the task names, even if they are numeric
+    Key(String),
+}
+
+impl TaskPath {
+    /// Append an object key, such as a task name.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.0.push(TaskPathItem::Key(key.into()));
+        self
+    }
+
+    /// Append an array index, such as the index of an item.
+    pub fn index(mut self, index: usize) -> Self {
+        self.0.push(TaskPathItem::Index(index));
+        self
+    }
+
+    /// Append the relative path.
+    pub fn join(&self, child: &Self) -> Self {
+        Self(self.0.iter().chain(&child.0).cloned().collect())
+    }
+
+    #[cfg(feature = "ui")]
+    fn get<'a>(&self, target: &'a Value) -> Option<&'a Value> {
+        self.0.iter().try_fold(target, |target, item| match item {
+            TaskPathItem::Index(index) => target.as_array()?.get(*index),
+            TaskPathItem::Key(key) => target.as_object()?.get(key),
+        })
+    }
+
+    #[cfg(feature = "ui")]
+    fn set(&self, target: &mut Value, value: Value) {
+        let target = self.0.iter().fold(target, |target, item| match item {
+            &TaskPathItem::Index(index) => {
+                if !target.is_array() {
+                    *target = Value::Array(Vec::default());
+                }
+                let items = target.as_array_mut().unwrap();
+                if index >= items.len() {
+                    items.resize(index + 1, Value::Null);
+                }
+                &mut items[index]
+            }
+            TaskPathItem::Key(key) => {
+                if !target.is_object() {
+                    *target = Value::Object(Default::default());
+                }
+                let map = target.as_object_mut().unwrap();
+                map.entry(key.clone()).or_insert(Value::Null)
+            }
+        });
+        *target = value;
+    }
+}
+
+/// Formats the path as a JSON pointer, such as `/loop/items/0/name`.
+impl fmt::Display for TaskPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.0 {
+            match item {
+                TaskPathItem::Index(index) => write!(f, "/{index}")?,
+                // Please see: https://datatracker.ietf.org/doc/html/rfc6901#section-4
+                TaskPathItem::Key(key) => {
+                    write!(f, "/{}", key.replace('~', "~0").replace('/', "~1"))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
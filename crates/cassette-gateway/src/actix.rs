@@ -116,12 +116,15 @@ fn build_core_services(scope: Scope) -> Scope {
         .service(robots_txt)
         .service(crate::routes::cassette::get)
         .service(crate::routes::cassette::list)
+        .service(crate::routes::schema::get)
 }
 
 fn build_plugin_services(scope: Scope) -> Scope {
     #[cfg(feature = "helm")]
     let scope = ::cassette_plugin_helm_api::build_services(scope);
     #[cfg(feature = "kubernetes")]
+    let scope = ::cassette_plugin_kubernetes_actor::build_services(scope);
+    #[cfg(feature = "kubernetes")]
     let scope = ::cassette_plugin_kubernetes_api::build_services(scope);
 
     scope
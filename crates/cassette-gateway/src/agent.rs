@@ -1,9 +1,16 @@
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::Arc,
+};
 
 use anyhow::Result;
-use cassette_core::cassette::{Cassette, CassetteRef};
+use cassette_core::{
+    cassette::{Cassette, CassetteRef},
+    components::{builtin, registry::TaskRegistry, validator::CassetteValidator},
+};
 use clap::Parser;
-use tracing::{instrument, Level};
+use schemars::schema::RootSchema;
+use tracing::{instrument, warn, Level};
 use uuid::Uuid;
 
 use crate::db::CassetteDB;
@@ -12,6 +19,8 @@ use crate::db::CassetteDB;
 pub struct Agent {
     args: AgentArgs,
     db: CassetteDB,
+    schema: Arc<RootSchema>,
+    validator: Arc<CassetteValidator>,
 }
 
 impl Agent {
@@ -23,9 +32,12 @@ impl Agent {
 
     #[instrument(level = Level::INFO, skip())]
     pub async fn try_new(args: AgentArgs) -> Result<Self> {
+        let registry = registry();
         Ok(Self {
             args,
             db: CassetteDB::default(),
+            schema: Arc::new(registry.schema()),
+            validator: Arc::new(registry.validator()),
         })
     }
 
@@ -44,12 +56,37 @@ impl Agent {
     pub(crate) const fn db(&self) -> &CassetteDB {
         &self.db
     }
+
+    pub(crate) fn schema(&self) -> &RootSchema {
+        &self.schema
+    }
+}
+
+/// Collect the task kinds, including the enabled plugins.
+fn registry() -> TaskRegistry {
+    let mut registry = builtin::registry();
+    #[cfg(feature = "kubernetes")]
+    ::cassette_plugin_kubernetes_core::list::register(&mut registry);
+    #[cfg(feature = "openai")]
+    ::cassette_plugin_openai_core::chat::register(&mut registry);
+    #[cfg(feature = "webcam")]
+    ::cassette_plugin_webcam_core::audio::register(&mut registry);
+    registry
 }
 
 impl Agent {
     #[instrument(level = Level::INFO, skip(self))]
     pub async fn get(&self, namespace: &str, id: Uuid) -> Option<Cassette> {
-        self.db.get(namespace, id).await
+        let cassette = self.db.get(namespace, id).await?;
+
+        // NOTE: the invalid tasks are still served, so that the player can report them as well
+        for diagnostic in self.validator.validate(&cassette.component) {
+            let name = &cassette.name;
+            let path = &diagnostic.path;
+            let message = &diagnostic.message;
+            warn!("Invalid cassette {namespace}/{name}: {path}: {message}");
+        }
+        Some(cassette)
     }
 
     #[instrument(level = Level::INFO, skip(self))]
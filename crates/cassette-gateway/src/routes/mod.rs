@@ -1 +1,2 @@
 pub mod cassette;
+pub mod schema;
@@ -0,0 +1,10 @@
+use actix_web::{get, web::Data, HttpResponse, Responder};
+use tracing::{instrument, Level};
+
+use crate::agent::Agent;
+
+#[instrument(level = Level::INFO, skip(agent))]
+#[get("/schema")]
+pub async fn get(agent: Data<Agent>) -> impl Responder {
+    HttpResponse::Ok().json(agent.schema())
+}
@@ -2,12 +2,18 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use cassette_core::{
     cassette::{Cassette, CassetteCrd, CassetteRef},
-    components::CassetteComponentCrd,
+    components::{CassetteComponentCrd, CassetteComponentSpec},
     net::DEFAULT_NAMESPACE,
+    task::CassetteTask,
 };
 use kube::ResourceExt;
+use serde_json::Value;
+use tracing::error;
 use uuid::Uuid;
 
+/// The task kinds whose specs nest the other tasks.
+const NESTED_KINDS: &[&str] = &["ForEach"];
+
 #[derive(Debug)]
 pub struct CassetteDB {
     cassettes: BTreeMap<String, BTreeSet<Cassette<String>>>,
@@ -46,15 +52,13 @@ impl CassetteDB {
             .get(namespace)
             .and_then(|cassettes| cassettes.iter().find(|cassette| cassette.id == id).cloned())?;
 
-        let scope = Scope {
-            namespace: namespace.into(),
-            name: component_name,
-        };
-        let component_id = self.components_scopes.get(&scope)?;
-        let component = self
-            .components
-            .get(component_id)
-            .map(|cr| cr.spec.clone())?;
+        let mut component = self.get_component(namespace, &component_name)?.clone();
+        let mut stack = vec![component_name];
+        let mut errors = Vec::default();
+        self.resolve_tasks(namespace, &mut component.tasks, &mut stack, &mut errors);
+        for error in errors {
+            error!("Failed to resolve cassette {namespace}/{name}: {error}");
+        }
 
         Some(Cassette {
             id,
@@ -126,6 +130,86 @@ impl CassetteDB {
 }
 
 impl CassetteDB {
+    fn get_component(&self, namespace: &str, name: &str) -> Option<&CassetteComponentSpec> {
+        let scope = Scope {
+            namespace: namespace.into(),
+            name: name.into(),
+        };
+        let component_id = self.components_scopes.get(&scope)?;
+        self.components.get(component_id).map(|cr| &cr.spec)
+    }
+
+    fn resolve_tasks(
+        &self,
+        namespace: &str,
+        tasks: &mut [CassetteTask],
+        stack: &mut Vec<String>,
+        errors: &mut Vec<String>,
+    ) {
+        for task in tasks {
+            self.resolve_task(namespace, task, stack, errors)
+        }
+    }
+
+    fn resolve_task(
+        &self,
+        namespace: &str,
+        task: &mut CassetteTask,
+        stack: &mut Vec<String>,
+        errors: &mut Vec<String>,
+    ) {
+        let spec = match &mut task.spec.0 {
+            Value::Object(spec) => spec,
+            _ => return,
+        };
+
+        // NOTE: the invalid specs are left as they are, as the validator reports them
+        let tasks = match task.kind.as_str() {
+            "Include" => {
+                let name = match spec.get("component") {
+                    Some(Value::String(name)) => name.clone(),
+                    _ => return,
+                };
+
+                // NOTE: missing or cyclic components are left unresolved, so that the player can report them too
+                if stack.contains(&name) {
+                    let cycle = stack.join(" -> ");
+                    errors.push(format!(
+                        "{:?}: Cyclic include: {cycle} -> {name}",
+                        task.name,
+                    ));
+                    return;
+                }
+                let mut tasks = match self.get_component(namespace, &name) {
+                    Some(component) => component.tasks.clone(),
+                    None => {
+                        errors.push(format!("{:?}: No such component: {name}", task.name));
+                        return;
+                    }
+                };
+
+                stack.push(name);
+                self.resolve_tasks(namespace, &mut tasks, stack, errors);
+                stack.pop();
+                tasks
+            }
+            kind if NESTED_KINDS.contains(&kind) => {
+                let mut tasks: Vec<CassetteTask> =
+                    match spec.get("tasks").cloned().map(::serde_json::from_value) {
+                        Some(Ok(tasks)) => tasks,
+                        Some(Err(_)) | None => return,
+                    };
+                self.resolve_tasks(namespace, &mut tasks, stack, errors);
+                tasks
+            }
+            _ => return,
+        };
+
+        if let Ok(tasks) = ::serde_json::to_value(tasks) {
+            spec.insert("tasks".into(), tasks);
+        }
+    }
+
     fn find_component(&self, namespace: &str, cassette: Cassette<String>) -> Option<CassetteRef> {
         let Cassette {
             id,
@@ -1,6 +1,9 @@
 use std::{env, fs::File, io::Write};
 
-use cassette_core::document::Document;
+use cassette_core::{
+    components::{builtin, registry::TaskRegistry},
+    document::Document,
+};
 use include_dir::{include_dir, Dir};
 
 const OUT_SRC: &str = "examples.yaml";
@@ -24,6 +27,22 @@ fn main() {
         .map(|doc| ::serde_yml::from_str(doc).expect("failed to parse example resource file"))
         .collect();
 
+    // Validate the components before embedding them
+    let validator = registry().validator();
+    for document in &documents {
+        if let Document::CassetteComponent(cr) = document {
+            let name = cr.metadata.name.as_deref().unwrap_or_default();
+            validator
+                .validate(&cr.spec)
+                .into_iter()
+                .for_each(|diagnostic| {
+                    let path = &diagnostic.path;
+                    let message = &diagnostic.message;
+                    println!("cargo:warning=Invalid cassette component {name}: {path}: {message}");
+                });
+        }
+    }
+
     // Documents -> JSON
     let documents_str =
         ::serde_json::to_string(&documents).expect("failed to serialize example resources to JSON");
@@ -37,3 +56,12 @@ fn main() {
         .expect("failed to write example resources");
     dst_file.flush().expect("failed to flush example resources");
 }
+
+/// Collect the task kinds of all the plugins, as the examples may use any of them.
+fn registry() -> TaskRegistry {
+    let mut registry = builtin::registry();
+    ::cassette_plugin_kubernetes_core::list::register(&mut registry);
+    ::cassette_plugin_openai_core::chat::register(&mut registry);
+    ::cassette_plugin_webcam_core::audio::register(&mut registry);
+    registry
+}
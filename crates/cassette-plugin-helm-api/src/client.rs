@@ -1,21 +1,71 @@
-use std::{collections::BTreeMap, process::Stdio};
+use std::{
+    collections::BTreeMap,
+    env, io,
+    path::{Component, Path},
+    process::Stdio,
+};
 
 use anyhow::{anyhow, bail, Result};
+use cassette_core::data::actor::SchemaActor;
 use cassette_plugin_helm_core::{
-    HelmDelete, HelmDeleteOutput, HelmPost, HelmPostOutput, HelmPut, HelmPutOutput,
+    HelmActor, HelmDelete, HelmDeleteOutput, HelmPost, HelmPostOutput, HelmPut, HelmPutOutput,
 };
 use cassette_plugin_kubernetes_api::UserClient;
 use itertools::Itertools;
 use k8s_openapi::api::core::v1::Secret;
 use kube::{api::ListParams, Api, Client, ResourceExt};
 use serde::Deserialize;
+use serde_json::Value;
 use tokio::{
+    fs,
     io::AsyncWriteExt,
     process::{Child, Command},
 };
 use tracing::{info, instrument, Level};
 use uuid::Uuid;
 
+/// The environment variable of the directory of the unpacked local charts.
+const ENV_CHART_ROOT: &str = "HELM_CHART_ROOT";
+
+/// The environment variable of the comma-separated hosts of the allowed chart repositories.
+///
+/// If not given, any HTTP(S) repository is allowed.
+const ENV_REPO_HOSTS: &str = "HELM_REPO_HOSTS";
+
+/// The maximum size of the chart archives to download.
+const MAX_CHART_ARCHIVE_SIZE: usize = 16 * 1024 * 1024;
+
+pub async fn actor(query: HelmActor) -> Result<SchemaActor> {
+    let HelmActor {
+        chart_name,
+        repo,
+        version,
+    } = query;
+
+    let (Some(chart_name), Some(repo)) = (chart_name, repo) else {
+        return Ok(::cassette_plugin_helm_core::actor());
+    };
+    validate_chart_name(&chart_name)?;
+
+    let ChartValues { schema, values } = match repo.strip_prefix("file://") {
+        // NOTE: the local charts are served only from the directory given by the operator
+        Some("") => {
+            let root = env::var_os(ENV_CHART_ROOT)
+                .ok_or_else(|| anyhow!("Local helm charts are not enabled: {chart_name}"))?;
+            load_chart_dir(&chart_name, Path::new(&root)).await?
+        }
+        Some(_) => bail!("Invalid local helm chart repository: {repo}"),
+        None => {
+            let url = fetch_chart_url(&chart_name, &repo, version.as_deref()).await?;
+            load_chart_archive(&chart_name, &url).await?
+        }
+    };
+    Ok(::cassette_plugin_helm_core::schema::build_actor(
+        schema.as_ref(),
+        &values,
+    ))
+}
+
 pub async fn delete(client: UserClient, id: Uuid, data: HelmDelete) -> Result<HelmDeleteOutput> {
     // Parse namespace
     let name = &data.name;
@@ -94,9 +144,11 @@ async fn execute(
         namespace,
         repo,
         values,
+        version,
     } = data;
 
-    let chart = fetch_chart_url(chart_name, repo).await?;
+    validate_chart_name(chart_name)?;
+    let chart = fetch_chart_url(chart_name, repo, version.as_deref()).await?;
     let namespace = namespace.clone().unwrap_or(user_namespace);
     let values = ::serde_json::to_string(values)?;
 
@@ -184,9 +236,52 @@ impl UpdateMode {
     }
 }
 
-async fn fetch_chart_url(chart_name: &str, repo: impl ToString) -> Result<String> {
+/// Check the chart name against the helm conventions: lowercase letters, digits and dashes.
+///
+/// The chart names are given to the commands, so they should not look like the options.
+fn validate_chart_name(chart_name: &str) -> Result<()> {
+    let is_valid = chart_name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && chart_name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric());
+    if is_valid {
+        Ok(())
+    } else {
+        bail!("Invalid chart name: {chart_name}")
+    }
+}
+
+/// Check whether the gateway is allowed to request the given URL of the chart repository.
+fn validate_repo_url(url: &str) -> Result<::reqwest::Url> {
+    let url: ::reqwest::Url = url
+        .parse()
+        .map_err(|_| anyhow!("Invalid helm chart repository: {url}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        bail!("Unsupported helm chart repository: {url}")
+    }
+
+    // NOTE: the repositories are requested by the gateway, which may reach the internal services
+    if let Some(hosts) = env::var_os(ENV_REPO_HOSTS) {
+        let hosts = hosts.to_string_lossy();
+        let host = url.host_str().unwrap_or_default();
+        if !hosts
+            .split(',')
+            .map(str::trim)
+            .any(|allowed| allowed == host)
+        {
+            bail!("Disallowed helm chart repository: {url}")
+        }
+    }
+    Ok(url)
+}
+
+async fn fetch_chart_url(chart_name: &str, repo: &str, version: Option<&str>) -> Result<String> {
+    let url = validate_repo_url(&format!("{}/index.yaml", repo.trim_end_matches('/')))?;
     let response = ::reqwest::Client::new()
-        .get(format!("{}/index.yaml", repo.to_string()))
+        .get(url)
         .send()
         .await
         .map_err(|_| anyhow!("Failed to request helm chart index: {chart_name}"))?;
@@ -204,14 +299,27 @@ async fn fetch_chart_url(chart_name: &str, repo: impl ToString) -> Result<String
         .filter(|(name, _)| *name == chart_name)
         .flat_map(|(_, entries)| entries)
         .filter(|entry| entry.r#type == "application")
+        .filter(|entry| version.map_or(true, |version| entry.version == version))
         .sorted_by_key(|entry| entry.version.clone())
         .last()
-        .ok_or_else(|| anyhow!("No such chart: {chart_name}"))?;
-    entry
+        .ok_or_else(|| match version {
+            Some(version) => anyhow!("No such chart: {chart_name} ({version})"),
+            None => anyhow!("No such chart: {chart_name}"),
+        })?;
+    let url = entry
         .urls
         .first()
-        .cloned()
-        .ok_or_else(|| anyhow!("No such chart URL: {chart_name}"))
+        .ok_or_else(|| anyhow!("No such chart URL: {chart_name}"))?;
+
+    // NOTE: the chart URLs may be relative to the repository
+    let url = if url.contains("://") {
+        url.clone()
+    } else {
+        format!("{}/{url}", repo.trim_end_matches('/'))
+    };
+
+    // NOTE: the chart URL is given to the commands, so it should not look like an option
+    validate_repo_url(&url).map(|url| url.to_string())
 }
 
 #[derive(Deserialize)]
@@ -228,3 +336,152 @@ struct HelmIndexEntry {
     urls: Vec<String>,
     version: String,
 }
+
+/// The files of a chart, which describe its values.
+struct ChartValues {
+    /// `values.schema.json`, if any
+    schema: Option<Value>,
+    /// `values.yaml`
+    values: Value,
+}
+
+impl ChartValues {
+    fn parse(schema: Option<Vec<u8>>, values: Option<Vec<u8>>, chart_name: &str) -> Result<Self> {
+        Ok(Self {
+            schema: schema
+                .map(|schema| ::serde_json::from_slice(&schema))
+                .transpose()
+                .map_err(|_| anyhow!("Failed to parse helm chart values schema: {chart_name}"))?,
+            values: values
+                .map(|values| ::serde_yml::from_slice(&values))
+                .transpose()
+                .map_err(|_| anyhow!("Failed to parse helm chart values: {chart_name}"))?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+async fn load_chart_dir(chart_name: &str, repo: &Path) -> Result<ChartValues> {
+    // NOTE: the chart name should not escape the repository
+    let mut components = Path::new(chart_name).components();
+    if !matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) {
+        bail!("Invalid chart name: {chart_name}")
+    }
+
+    let dir = repo.join(chart_name);
+    if !fs::try_exists(dir.join("Chart.yaml")).await? {
+        bail!("No such chart: {chart_name}")
+    }
+
+    async fn read_optional(path: &Path) -> Result<Option<Vec<u8>>> {
+        match fs::read(path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    let schema = read_optional(&dir.join("values.schema.json")).await?;
+    let values = read_optional(&dir.join("values.yaml")).await?;
+    ChartValues::parse(schema, values, chart_name)
+}
+
+async fn load_chart_archive(chart_name: &str, url: &str) -> Result<ChartValues> {
+    let mut response = ::reqwest::Client::new()
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|_| anyhow!("Failed to request helm chart: {chart_name}"))?;
+
+    // NOTE: the archive is kept in memory, so its size should be limited
+    let too_large = || anyhow!("Too large helm chart: {chart_name}");
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_CHART_ARCHIVE_SIZE as u64)
+    {
+        return Err(too_large());
+    }
+    let mut archive = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|_| anyhow!("Failed to get helm chart: {chart_name}"))?
+    {
+        if archive.len() + chunk.len() > MAX_CHART_ARCHIVE_SIZE {
+            return Err(too_large());
+        }
+        archive.extend_from_slice(&chunk);
+    }
+
+    let schema = extract_chart_file(&archive, chart_name, "values.schema.json").await?;
+    let values = extract_chart_file(&archive, chart_name, "values.yaml").await?;
+    ChartValues::parse(schema, values, chart_name)
+}
+
+/// Extract a file from the chart archive, returning `None` if it does not exist.
+async fn extract_chart_file(
+    archive: &[u8],
+    chart_name: &str,
+    name: &str,
+) -> Result<Option<Vec<u8>>> {
+    let mut command = Command::new("tar")
+        .arg("--extract")
+        .arg("--gzip")
+        .arg("--to-stdout")
+        .arg("--file")
+        .arg("-")
+        // NOTE: the member names should never be parsed as the options
+        .arg("--")
+        .arg(format!("{chart_name}/{name}"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // NOTE: write and read at once, as the pipes may be full otherwise
+    let mut pipe = command.stdin.take();
+    let write = async move {
+        if let Some(pipe) = pipe.as_mut() {
+            pipe.write_all(archive).await?;
+        }
+        drop(pipe);
+        Ok::<_, io::Error>(())
+    };
+    let (written, output) = ::tokio::join!(write, command.wait_with_output());
+
+    let output = output?;
+    if output.status.success() {
+        written?;
+        Ok(Some(output.stdout))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chart_names() {
+        assert!(validate_chart_name("nginx").is_ok());
+        assert!(validate_chart_name("kube-prometheus-stack2").is_ok());
+        assert!(validate_chart_name("").is_err());
+        assert!(validate_chart_name("--to-command=sh").is_err());
+        assert!(validate_chart_name("-nginx").is_err());
+        assert!(validate_chart_name("Nginx").is_err());
+        assert!(validate_chart_name("../nginx").is_err());
+    }
+
+    #[test]
+    fn repo_urls() {
+        assert!(validate_repo_url("https://charts.example.com/index.yaml").is_ok());
+        assert!(validate_repo_url("http://charts.example.com/nginx-1.0.0.tgz").is_ok());
+        assert!(validate_repo_url("file:///etc/passwd").is_err());
+        assert!(validate_repo_url("--post-renderer=sh").is_err());
+    }
+}
@@ -10,12 +10,13 @@ use actix_web::{
 use anyhow::Result;
 use cassette_core::{
     data::{
+        column::DataColumnType,
         csv::CsvTable,
-        table::{DataTable, DataTableLog, DataTableSource},
+        table::{DataTable, DataTableLog, DataTableLogKind, DataTableSource},
     },
     result::HttpResult,
 };
-use cassette_plugin_helm_core::{HelmDelete, HelmList, HelmPost, HelmPut};
+use cassette_plugin_helm_core::{HelmActor, HelmDelete, HelmList, HelmPost, HelmPut};
 use cassette_plugin_kubernetes_api::UserClient;
 use cassette_plugin_kubernetes_core::user::{UserRoleSpec, UserSpec};
 use itertools::Itertools;
@@ -36,8 +37,15 @@ pub fn build_services(scope: Scope) -> Scope {
 
 #[instrument(level = Level::INFO, skip_all)]
 #[get("/helm/_actor")]
-async fn actor() -> impl Responder {
-    HttpResponse::from(HttpResult::Ok(::cassette_plugin_helm_core::actor()))
+async fn actor(
+    client: Data<Client>,
+    request: HttpRequest,
+    query: Query<HelmActor>,
+) -> impl Responder {
+    match UserClient::from_request(client, &request).await {
+        Ok(_) => HttpResponse::from(HttpResult::from(self::client::actor(query.0).await)),
+        Err(error) => HttpResponse::Unauthorized().json(HttpResult::<()>::Err(error.to_string())),
+    }
 }
 
 #[instrument(level = Level::INFO, skip_all)]
@@ -113,6 +121,15 @@ async fn list(
             "state".into(),
             "created_at".into(),
         ];
+        let types = vec![
+            DataColumnType::String,
+            DataColumnType::String,
+            DataColumnType::String,
+            DataColumnType::String,
+            DataColumnType::String,
+            DataColumnType::String,
+            DataColumnType::DateTime,
+        ];
         let mut records = list
             .items
             .into_iter()
@@ -145,9 +162,10 @@ async fn list(
             name,
             data: Rc::new(DataTableSource::Csv(CsvTable {
                 headers,
+                types,
                 records: Rc::new(records),
             })),
-            log: DataTableLog::default(),
+            log: DataTableLog::new(DataTableLogKind::Loaded),
         })
     }
 
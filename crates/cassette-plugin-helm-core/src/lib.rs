@@ -1,3 +1,5 @@
+pub mod schema;
+
 use cassette_core::data::actor::SchemaActor;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -10,6 +12,19 @@ pub fn actor() -> SchemaActor {
     }
 }
 
+/// The chart to derive the actor schema from, if any.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HelmActor {
+    #[serde(default)]
+    pub chart_name: Option<String>,
+    /// The HTTP(S) chart repository, or `file://` for the local charts under `HELM_CHART_ROOT`
+    #[serde(default)]
+    pub repo: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct HelmDelete {
@@ -43,6 +58,9 @@ pub struct HelmPut {
     pub repo: String,
     #[serde(default)]
     pub values: Value,
+    /// The chart version, or the latest one if not given
+    #[serde(default)]
+    pub version: Option<String>,
 }
 
 pub type HelmPutOutput = String;
@@ -0,0 +1,173 @@
+use cassette_core::data::actor::{
+    object, string, Schema, SchemaActor, SchemaArray, SchemaPath, SchemaType,
+};
+use serde_json::Value;
+
+/// Convert the chart's `values.schema.json` into the actor schema,
+/// filling the defaults from its `values.yaml`.
+///
+/// If the chart has no schema, the fields are guessed from the default values.
+pub fn build_actor(values_schema: Option<&Value>, values: &Value) -> SchemaActor {
+    let fields = match values_schema {
+        Some(schema) => SchemaArray::from_json_schema(schema, values),
+        None => SchemaArray::from_values(values),
+    };
+
+    let schema = SchemaArray(vec![
+        Schema::new(
+            "Name",
+            SchemaPath::from_key("name"),
+            SchemaType::String(string::SchemaSpec {
+                required: true,
+                format: Some(string::SchemaStringFormat::Dns1123Label),
+                ..Default::default()
+            }),
+        ),
+        Schema {
+            computed_default: Some("~/user/namespace".into()),
+            ..Schema::new(
+                "Namespace",
+                SchemaPath::from_key("namespace"),
+                SchemaType::String(string::SchemaSpec {
+                    required: true,
+                    format: Some(string::SchemaStringFormat::Dns1123Label),
+                    ..Default::default()
+                }),
+            )
+        },
+        Schema::new(
+            "Values",
+            SchemaPath::from_key("values"),
+            SchemaType::Object(object::SchemaSpec { fields }),
+        ),
+    ]);
+
+    SchemaActor {
+        create: Some(schema.clone()),
+        update: Some(schema),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cassette_core::data::actor::{boolean, json, number, r#enum, secret};
+
+    use super::*;
+
+    const VALUES_SCHEMA: &str = include_str!("../tests/charts/sample/values.schema.json");
+    const VALUES: &str = include_str!("../tests/charts/sample/values.yaml");
+
+    fn load_values() -> Value {
+        ::serde_yml::from_str(VALUES).expect("failed to parse the sample values")
+    }
+
+    fn find<'a>(fields: &'a SchemaArray, name: &str) -> &'a SchemaType {
+        fields
+            .0
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| &field.ty)
+            .unwrap_or_else(|| panic!("no such field: {name}"))
+    }
+
+    fn pod_annotations() -> json::SchemaSpec {
+        let mut annotations = ::serde_json::Map::default();
+        annotations.insert("prometheus.io/scrape".into(), "true".into());
+        json::SchemaSpec {
+            default: Some(annotations),
+            required: false,
+        }
+    }
+
+    fn find_object<'a>(fields: &'a SchemaArray, name: &str) -> &'a SchemaArray {
+        match find(fields, name) {
+            SchemaType::Object(object::SchemaSpec { fields }) => fields,
+            ty => panic!("not an object: {name}: {ty:?}"),
+        }
+    }
+
+    #[test]
+    fn build_actor_from_values_schema() {
+        let schema: Value = ::serde_json::from_str(VALUES_SCHEMA).unwrap();
+        let actor = build_actor(Some(&schema), &load_values());
+        assert_eq!(actor.create, actor.update);
+
+        let fields = actor.create.unwrap();
+        let names: Vec<_> = fields.0.iter().map(|field| field.name.as_str()).collect();
+        assert_eq!(names, ["Name", "Namespace", "Values"]);
+
+        let values = find_object(&fields, "Values");
+        assert_eq!(
+            find(values, "Replicas"),
+            &SchemaType::Number(number::SchemaSpec {
+                default: Some(2.into()),
+                required: false,
+                min: Some(1.0),
+                max: None,
+                step: Some(1.0),
+            }),
+        );
+        assert_eq!(
+            find(values, "adminPassword"),
+            &SchemaType::Secret(secret::SchemaSpec {
+                required: true,
+                min_length: Some(8),
+            }),
+        );
+
+        assert_eq!(
+            find(values, "podAnnotations"),
+            &SchemaType::Json(pod_annotations()),
+        );
+
+        let image = find_object(values, "image");
+        assert_eq!(
+            find(image, "repository"),
+            &SchemaType::String(string::SchemaSpec {
+                default: Some("nginx".into()),
+                ..Default::default()
+            }),
+        );
+
+        let service = find_object(values, "service");
+        assert_eq!(
+            find(service, "type"),
+            &SchemaType::Enum(r#enum::SchemaSpec {
+                choices: vec!["ClusterIP".into(), "NodePort".into(), "LoadBalancer".into()],
+                default: Some("ClusterIP".into()),
+                required: false,
+            }),
+        );
+
+        let ingress = find_object(values, "ingress");
+        assert_eq!(
+            find(ingress, "enabled"),
+            &SchemaType::Boolean(boolean::SchemaSpec {
+                default: Some(false),
+            }),
+        );
+    }
+
+    #[test]
+    fn build_actor_from_values() {
+        let actor = build_actor(None, &load_values());
+
+        let fields = actor.create.unwrap();
+        let values = find_object(&fields, "Values");
+        assert_eq!(
+            find(values, "replicaCount"),
+            &SchemaType::Number(number::SchemaSpec {
+                default: Some(2.into()),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            find(find_object(values, "image"), "tag"),
+            &SchemaType::String(string::SchemaSpec {
+                default: Some("1.27".into()),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(fields.0[2].path.to_string(), "/values");
+    }
+}
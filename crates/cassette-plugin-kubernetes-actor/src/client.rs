@@ -0,0 +1,468 @@
+use std::{fmt, rc::Rc};
+
+use anyhow::{anyhow, bail, Result};
+use cassette_core::data::{
+    actor::{string, Schema, SchemaActor, SchemaArray, SchemaPathItem, SchemaType},
+    column::DataColumnType,
+    csv::CsvTable,
+    table::{DataTable, DataTableLog, DataTableLogKind, DataTableSource},
+};
+use cassette_plugin_kubernetes_api::UserClient;
+use cassette_plugin_kubernetes_core::user::{UserRoleSpec, UserSpec};
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::{
+    CustomResourceDefinition, CustomResourceDefinitionSpec,
+};
+use kube::{
+    api::{ApiResource, DeleteParams, DynamicObject, ListParams, Patch, PatchParams, PostParams},
+    core::GroupVersionKind,
+    Api, Client, ResourceExt,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{info, instrument, Level};
+use uuid::Uuid;
+
+/// A kubernetes kind, such as `cassette.ulagbulag.io/v1alpha1/cassettes`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KubernetesKind {
+    pub group: String,
+    pub version: String,
+    pub plural: String,
+}
+
+impl fmt::Display for KubernetesKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            group,
+            version,
+            plural,
+        } = self;
+        write!(f, "{group}/{version}/{plural}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KubernetesDelete {
+    pub name: String,
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KubernetesList {
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+pub async fn actor(kube: &Client, kind: &KubernetesKind) -> Result<SchemaActor> {
+    load_resource(kube, kind).await?.actor()
+}
+
+#[instrument(level = Level::INFO, skip(kube, client, data))]
+pub async fn create(
+    kube: &Client,
+    client: UserClient,
+    kind: &KubernetesKind,
+    data: Value,
+) -> Result<String> {
+    let resource = load_resource(kube, kind).await?;
+    let object = resource.build_object(&client.spec, data)?;
+    let name = object.name_any();
+
+    info!("Creating {kind}: {name}");
+    let api = resource.api(client.kube, object.namespace().as_deref());
+    api.create(&PostParams::default(), &object).await?;
+    Ok(format!("Created {name}"))
+}
+
+#[instrument(level = Level::INFO, skip(kube, client, data))]
+pub async fn apply(
+    kube: &Client,
+    client: UserClient,
+    kind: &KubernetesKind,
+    id: Uuid,
+    data: Value,
+) -> Result<String> {
+    let resource = load_resource(kube, kind).await?;
+    let object = resource.build_object(&client.spec, data)?;
+    let name = object.name_any();
+
+    // Validate data
+    let api = resource.api(client.kube, object.namespace().as_deref());
+    validate_id(&api, id, &name).await?;
+
+    // NOTE: the fields owned by the other managers, such as the controllers, are not overwritten
+    info!("Applying {kind}: {name}");
+    let pp = PatchParams::apply("cassette");
+    match api.patch(&name, &pp, &Patch::Apply(&object)).await {
+        Ok(_) => Ok(format!("Applied {name}")),
+        Err(::kube::Error::Api(error)) if error.code == 409 => {
+            bail!("Conflicted {name}: {}", error.message)
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+#[instrument(level = Level::INFO, skip(kube, client, data))]
+pub async fn delete(
+    kube: &Client,
+    client: UserClient,
+    kind: &KubernetesKind,
+    id: Uuid,
+    data: KubernetesDelete,
+) -> Result<String> {
+    let resource = load_resource(kube, kind).await?;
+    let KubernetesDelete { name, namespace } = data;
+    let namespace = namespace.unwrap_or_else(|| client.spec.namespace.clone());
+
+    // Validate data
+    let api = resource.api(client.kube, Some(&namespace));
+    validate_id(&api, id, &name).await?;
+
+    info!("Deleting {kind}: {name}");
+    api.delete(&name, &DeleteParams::default()).await?;
+    Ok(format!("Deleted {name}"))
+}
+
+pub async fn list(
+    kube: &Client,
+    client: UserClient,
+    kind: &KubernetesKind,
+    query: KubernetesList,
+) -> Result<DataTable> {
+    let UserClient {
+        kube: user_kube,
+        spec:
+            UserSpec {
+                name,
+                namespace: user_namespace,
+                role: UserRoleSpec { is_admin },
+                ..
+            },
+    } = client;
+
+    // Load data
+
+    let resource = load_resource(kube, kind).await?;
+    let namespace = if is_admin {
+        query.namespace
+    } else {
+        Some(user_namespace)
+    };
+    let api = resource.api(user_kube, namespace.as_deref());
+    let list = api.list_metadata(&ListParams::default()).await?;
+
+    // Create a data table
+
+    let name = format!("{}-{name}", resource.api_resource.plural);
+    let headers = vec![
+        "id".into(),
+        "namespace".into(),
+        "name".into(),
+        "created_at".into(),
+    ];
+    let types = vec![
+        DataColumnType::String,
+        DataColumnType::String,
+        DataColumnType::String,
+        DataColumnType::DateTime,
+    ];
+    let records = list
+        .items
+        .into_iter()
+        .filter_map(|item| {
+            Some(vec![
+                Value::String(item.uid()?),
+                item.namespace().map(Value::String).unwrap_or_default(),
+                Value::String(item.name_any()),
+                Value::String(item.creation_timestamp()?.0.to_rfc3339()),
+            ])
+        })
+        .collect();
+
+    Ok(DataTable {
+        name,
+        data: Rc::new(DataTableSource::Csv(CsvTable {
+            headers,
+            types,
+            records: Rc::new(records),
+        })),
+        log: DataTableLog::new(DataTableLogKind::Loaded),
+    })
+}
+
+async fn validate_id(api: &Api<DynamicObject>, id: Uuid, name: &str) -> Result<()> {
+    let object = api
+        .get_metadata_opt(name)
+        .await
+        .map_err(|_| anyhow!("No such object: {name}"))?;
+
+    let uid = Some(id.to_string());
+    match object {
+        Some(object) if object.uid() == uid => Ok(()),
+        _ => bail!("No such object: {name}"),
+    }
+}
+
+struct KindResource {
+    api_resource: ApiResource,
+    namespaced: bool,
+    /// The `openAPIV3Schema` of the served version
+    schema: Value,
+}
+
+impl KindResource {
+    fn api(&self, kube: Client, namespace: Option<&str>) -> Api<DynamicObject> {
+        match namespace.filter(|_| self.namespaced) {
+            Some(namespace) => Api::namespaced_with(kube, namespace, &self.api_resource),
+            None => Api::all_with(kube, &self.api_resource),
+        }
+    }
+
+    /// Derive the form fields from the schema, with the name and the namespace (if namespaced).
+    fn actor(&self) -> Result<SchemaActor> {
+        let mut fields = vec![Schema::new(
+            "Name",
+            "/metadata/name".parse()?,
+            SchemaType::String(string::SchemaSpec {
+                required: true,
+                format: Some(string::SchemaStringFormat::Dns1123Subdomain),
+                ..Default::default()
+            }),
+        )];
+        if self.namespaced {
+            fields.push(Schema {
+                computed_default: Some("~/user/namespace".into()),
+                ..Schema::new(
+                    "Namespace",
+                    "/metadata/namespace".parse()?,
+                    SchemaType::String(string::SchemaSpec {
+                        required: true,
+                        format: Some(string::SchemaStringFormat::Dns1123Label),
+                        ..Default::default()
+                    }),
+                )
+            });
+        }
+
+        // NOTE: the status is managed by the controllers, and the others are given above
+        const RESERVED_KEYS: &[&str] = &["apiVersion", "kind", "metadata", "status"];
+        fields.extend(
+            SchemaArray::from_json_schema(&self.schema, &Value::Null)
+                .0
+                .into_iter()
+                .filter(|field| match field.path.0.first() {
+                    Some(SchemaPathItem::Object(key)) => !RESERVED_KEYS.contains(&key.as_str()),
+                    _ => true,
+                }),
+        );
+
+        let schema = SchemaArray(fields);
+        Ok(SchemaActor {
+            create: Some(schema.clone()),
+            update: Some(schema),
+        })
+    }
+
+    /// Build the object from the submitted form, filling the type and the default namespace.
+    fn build_object(&self, user: &UserSpec, mut data: Value) -> Result<DynamicObject> {
+        let Value::Object(map) = &mut data else {
+            bail!("Invalid object: {}", self.api_resource.kind)
+        };
+        map.insert(
+            "apiVersion".into(),
+            Value::String(self.api_resource.api_version.clone()),
+        );
+        map.insert("kind".into(), Value::String(self.api_resource.kind.clone()));
+
+        let mut object: DynamicObject =
+            ::serde_json::from_value(data).map_err(|error| anyhow!("Invalid object: {error}"))?;
+        if self.namespaced {
+            object
+                .metadata
+                .namespace
+                .get_or_insert_with(|| user.namespace.clone());
+        } else {
+            object.metadata.namespace = None;
+        }
+        Ok(object)
+    }
+}
+
+async fn load_resource(kube: &Client, kind: &KubernetesKind) -> Result<KindResource> {
+    let KubernetesKind {
+        group,
+        version,
+        plural,
+    } = kind;
+
+    let api = Api::<CustomResourceDefinition>::all(kube.clone());
+    let name = format!("{plural}.{group}");
+    let CustomResourceDefinitionSpec {
+        names,
+        scope,
+        versions,
+        ..
+    } = api
+        .get_opt(&name)
+        .await
+        .map_err(|_| anyhow!("Failed to get kind: {kind}"))?
+        .ok_or_else(|| anyhow!("No such kind: {kind}"))?
+        .spec;
+
+    let schema = versions
+        .into_iter()
+        .find(|spec| spec.name == *version && spec.served)
+        .ok_or_else(|| anyhow!("No such version: {kind}"))?
+        .schema
+        .and_then(|schema| schema.open_api_v3_schema)
+        .map(::serde_json::to_value)
+        .transpose()?
+        .unwrap_or_default();
+
+    let gvk = GroupVersionKind::gvk(group, version, &names.kind);
+    Ok(KindResource {
+        api_resource: ApiResource::from_gvk_with_plural(&gvk, &names.plural),
+        namespaced: scope == "Namespaced",
+        schema,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn resource(namespaced: bool) -> KindResource {
+        let gvk = GroupVersionKind::gvk("example.com", "v1", "Widget");
+        KindResource {
+            api_resource: ApiResource::from_gvk_with_plural(&gvk, "widgets"),
+            namespaced,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "apiVersion": { "type": "string" },
+                    "kind": { "type": "string" },
+                    "metadata": { "type": "object" },
+                    "spec": {
+                        "type": "object",
+                        "required": ["image"],
+                        "properties": {
+                            "image": { "type": "string" },
+                            "replicas": { "type": "integer", "default": 1 },
+                        },
+                    },
+                    "status": {
+                        "type": "object",
+                        "properties": {
+                            "phase": { "type": "string" },
+                        },
+                    },
+                },
+            }),
+        }
+    }
+
+    fn user() -> UserSpec {
+        UserSpec {
+            metadata: Default::default(),
+            name: "alice".into(),
+            namespace: "alice-ns".into(),
+            role: UserRoleSpec::default(),
+            token: Default::default(),
+        }
+    }
+
+    fn fields(actor: &SchemaActor) -> Vec<String> {
+        actor
+            .create
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .map(|field| format!("{} {}", field.name, field.path))
+            .collect()
+    }
+
+    #[test]
+    fn actor_namespaced() {
+        let actor = resource(true).actor().unwrap();
+        assert_eq!(actor.create, actor.update);
+
+        // NOTE: the reserved keys are replaced by the name and the namespace
+        assert_eq!(
+            fields(&actor),
+            [
+                "Name /metadata/name",
+                "Namespace /metadata/namespace",
+                "spec /spec",
+            ],
+        );
+
+        let namespace = &actor.create.as_ref().unwrap().0[1];
+        assert_eq!(
+            namespace.computed_default.as_deref(),
+            Some("~/user/namespace"),
+        );
+    }
+
+    #[test]
+    fn actor_cluster_scoped() {
+        let actor = resource(false).actor().unwrap();
+        assert_eq!(fields(&actor), ["Name /metadata/name", "spec /spec"]);
+    }
+
+    #[test]
+    fn build_object_namespaced() {
+        let resource = resource(true);
+
+        let object = resource
+            .build_object(&user(), json!({ "metadata": { "name": "my-widget" } }))
+            .unwrap();
+        let types = object.types.as_ref().unwrap();
+        assert_eq!(types.api_version, "example.com/v1");
+        assert_eq!(types.kind, "Widget");
+        assert_eq!(object.name_any(), "my-widget");
+        assert_eq!(object.namespace().as_deref(), Some("alice-ns"));
+
+        let object = resource
+            .build_object(
+                &user(),
+                json!({ "metadata": { "name": "my-widget", "namespace": "shared" } }),
+            )
+            .unwrap();
+        assert_eq!(object.namespace().as_deref(), Some("shared"));
+    }
+
+    #[test]
+    fn build_object_cluster_scoped() {
+        let object = resource(false)
+            .build_object(
+                &user(),
+                json!({
+                    "apiVersion": "other.com/v2",
+                    "metadata": { "name": "my-widget", "namespace": "shared" },
+                    "spec": { "image": "nginx" },
+                }),
+            )
+            .unwrap();
+
+        // NOTE: the type is always taken from the kind, not from the form
+        assert_eq!(object.types.as_ref().unwrap().api_version, "example.com/v1");
+        assert_eq!(object.namespace(), None);
+        assert_eq!(object.data["spec"]["image"], "nginx");
+    }
+
+    #[test]
+    fn build_object_invalid() {
+        let resource = resource(true);
+        assert!(resource.build_object(&user(), json!("my-widget")).is_err());
+        assert!(resource
+            .build_object(&user(), json!({ "metadata": "my-widget" }))
+            .is_err());
+    }
+}
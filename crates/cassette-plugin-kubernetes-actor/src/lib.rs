@@ -0,0 +1,116 @@
+mod client;
+
+use actix_web::{
+    get, post, put,
+    web::{Data, Json, Path, Query},
+    HttpRequest, HttpResponse, Responder, Scope,
+};
+use cassette_core::result::HttpResult;
+use cassette_plugin_kubernetes_api::UserClient;
+use kube::Client;
+use serde_json::Value;
+use tracing::{instrument, Level};
+use uuid::Uuid;
+
+use crate::client::{KubernetesDelete, KubernetesKind, KubernetesList};
+
+pub fn build_services(scope: Scope) -> Scope {
+    scope
+        .service(actor)
+        .service(delete)
+        .service(list)
+        .service(post)
+        .service(put)
+}
+
+#[instrument(level = Level::INFO, skip_all)]
+#[get("/kube-actor/{group}/{version}/{plural}/_actor")]
+async fn actor(
+    client: Data<Client>,
+    request: HttpRequest,
+    kind: Path<KubernetesKind>,
+) -> impl Responder {
+    // NOTE: the CRDs are loaded by the gateway, as the users may not be allowed to read them
+    match UserClient::from_request(client.clone(), &request).await {
+        Ok(_) => HttpResponse::from(HttpResult::from(self::client::actor(&client, &kind).await)),
+        Err(error) => HttpResponse::Unauthorized().json(HttpResult::<()>::Err(error.to_string())),
+    }
+}
+
+#[instrument(level = Level::INFO, skip_all)]
+#[post("/kube-actor/{group}/{version}/{plural}/{id}/delete")]
+async fn delete(
+    client: Data<Client>,
+    request: HttpRequest,
+    path: Path<(String, String, String, Uuid)>,
+    data: Json<KubernetesDelete>,
+) -> impl Responder {
+    let (group, version, plural, id) = path.into_inner();
+    let kind = KubernetesKind {
+        group,
+        version,
+        plural,
+    };
+
+    match UserClient::from_request(client.clone(), &request).await {
+        Ok(user) => HttpResponse::from(HttpResult::from(
+            self::client::delete(&client, user, &kind, id, data.0).await,
+        )),
+        Err(error) => HttpResponse::Unauthorized().json(HttpResult::<()>::Err(error.to_string())),
+    }
+}
+
+#[instrument(level = Level::INFO, skip_all)]
+#[get("/kube-actor/{group}/{version}/{plural}")]
+async fn list(
+    client: Data<Client>,
+    request: HttpRequest,
+    kind: Path<KubernetesKind>,
+    query: Query<KubernetesList>,
+) -> impl Responder {
+    match UserClient::from_request(client.clone(), &request).await {
+        Ok(user) => HttpResponse::from(HttpResult::from(
+            self::client::list(&client, user, &kind, query.0).await,
+        )),
+        Err(error) => HttpResponse::Unauthorized().json(HttpResult::<()>::Err(error.to_string())),
+    }
+}
+
+#[instrument(level = Level::INFO, skip_all)]
+#[post("/kube-actor/{group}/{version}/{plural}/{id}")]
+async fn post(
+    client: Data<Client>,
+    request: HttpRequest,
+    path: Path<(String, String, String, Uuid)>,
+    data: Json<Value>,
+) -> impl Responder {
+    let (group, version, plural, id) = path.into_inner();
+    let kind = KubernetesKind {
+        group,
+        version,
+        plural,
+    };
+
+    match UserClient::from_request(client.clone(), &request).await {
+        Ok(user) => HttpResponse::from(HttpResult::from(
+            self::client::apply(&client, user, &kind, id, data.0).await,
+        )),
+        Err(error) => HttpResponse::Unauthorized().json(HttpResult::<()>::Err(error.to_string())),
+    }
+}
+
+#[instrument(level = Level::INFO, skip_all)]
+#[put("/kube-actor/{group}/{version}/{plural}")]
+async fn put(
+    client: Data<Client>,
+    request: HttpRequest,
+    kind: Path<KubernetesKind>,
+    data: Json<Value>,
+) -> impl Responder {
+    match UserClient::from_request(client.clone(), &request).await {
+        Ok(user) => HttpResponse::from(HttpResult::from(
+            self::client::create(&client, user, &kind, data.0).await,
+        )),
+        Err(error) => HttpResponse::Unauthorized().json(HttpResult::<()>::Err(error.to_string())),
+    }
+}
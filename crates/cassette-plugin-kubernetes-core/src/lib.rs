@@ -4,4 +4,6 @@ pub mod api;
 pub mod client;
 #[cfg(feature = "ui")]
 pub mod hooks;
+#[cfg(feature = "list")]
+pub mod list;
 pub mod user;
@@ -0,0 +1,18 @@
+use cassette_core::components::registry::TaskRegistry;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+/// Declare the `KubernetesList` task kind, without the renderer.
+pub fn register(registry: &mut TaskRegistry) {
+    registry.register::<Spec>("KubernetesList");
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    pub api_version: String,
+    pub kind: String,
+}
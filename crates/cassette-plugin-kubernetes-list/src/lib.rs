@@ -1,20 +1,23 @@
 use cassette_core::{
     cassette::{CassetteContext, GenericCassetteTaskHandle},
-    components::ComponentRenderer,
+    components::{registry::TaskRegistry, ComponentRenderer},
     net::fetch::FetchState,
     prelude::*,
     task::{TaskResult, TaskState},
 };
-use cassette_plugin_kubernetes_core::hooks::{use_kubernetes_api, use_kubernetes_list};
+use cassette_plugin_kubernetes_core::{
+    hooks::{use_kubernetes_api, use_kubernetes_list},
+    list::Spec,
+};
 use kube_core::{params::ListParams, DynamicObject};
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    api_version: String,
-    kind: String,
+pub fn register(registry: &mut TaskRegistry) {
+    ::cassette_plugin_kubernetes_core::list::register(registry);
+    registry
+        .insert("KubernetesList")
+        .with_renderer::<State, Spec>();
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
@@ -3,26 +3,20 @@ mod schema;
 
 use cassette_core::{
     cassette::{CassetteContext, GenericCassetteTaskHandle},
-    components::ComponentRenderer,
+    components::{registry::TaskRegistry, ComponentRenderer},
     net::fetch::FetchState,
     prelude::*,
     task::{TaskResult, TaskState},
 };
+use cassette_plugin_openai_core::chat::Spec;
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
-use crate::schema::{Message, Request, Role};
+use crate::schema::{Message, Role};
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    base_url: String,
-
-    #[serde(default)]
-    message: Option<String>,
-
-    #[serde(default, flatten)]
-    request: Request,
+pub fn register(registry: &mut TaskRegistry) {
+    ::cassette_plugin_openai_core::chat::register(registry);
+    registry.insert("OpenAIChat").with_renderer::<State, Spec>();
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
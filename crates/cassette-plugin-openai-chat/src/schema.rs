@@ -1,64 +1,8 @@
 use std::collections::VecDeque;
 
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub struct Request {
-    #[serde(default = "Request::default_model")]
-    pub model: String,
-    #[serde(default, flatten)]
-    pub options: RequestOptions,
-    #[serde(default)]
-    pub messages: Vec<Message>,
-}
-
-impl Default for Request {
-    fn default() -> Self {
-        Self {
-            model: Self::default_model(),
-            options: Default::default(),
-            messages: Default::default(),
-        }
-    }
-}
-
-impl Request {
-    fn default_model() -> String {
-        "any".into()
-    }
-}
-
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub struct RequestOptions {
-    #[serde(default = "RequestOptions::default_max_tokens")]
-    pub max_tokens: u32,
-    #[serde(default)]
-    pub stream: Option<bool>,
-}
-
-impl Default for RequestOptions {
-    fn default() -> Self {
-        Self {
-            max_tokens: Self::default_max_tokens(),
-            stream: Default::default(),
-        }
-    }
-}
-
-impl RequestOptions {
-    const fn default_max_tokens() -> u32 {
-        1000
-    }
-}
-
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub struct Message {
-    pub role: Role,
-    pub content: String,
-}
+pub use cassette_plugin_openai_core::chat::{Message, Request, Role};
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -78,14 +22,6 @@ pub enum MessageFinishReason {
     Length,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Role {
-    Assistant,
-    User,
-    System,
-}
-
 #[derive(Clone, Debug, Default, PartialEq, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Response {
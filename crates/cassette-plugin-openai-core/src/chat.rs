@@ -0,0 +1,89 @@
+use cassette_core::components::registry::TaskRegistry;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+/// Declare the `OpenAIChat` task kind, without the renderer.
+pub fn register(registry: &mut TaskRegistry) {
+    registry.register::<Spec>("OpenAIChat");
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    pub base_url: String,
+
+    #[serde(default)]
+    pub message: Option<String>,
+
+    #[serde(default, flatten)]
+    pub request: Request,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Request {
+    #[serde(default = "Request::default_model")]
+    pub model: String,
+    #[serde(default, flatten)]
+    pub options: RequestOptions,
+    #[serde(default)]
+    pub messages: Vec<Message>,
+}
+
+impl Default for Request {
+    fn default() -> Self {
+        Self {
+            model: Self::default_model(),
+            options: Default::default(),
+            messages: Default::default(),
+        }
+    }
+}
+
+impl Request {
+    fn default_model() -> String {
+        "any".into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RequestOptions {
+    #[serde(default = "RequestOptions::default_max_tokens")]
+    pub max_tokens: u32,
+    #[serde(default)]
+    pub stream: Option<bool>,
+}
+
+impl Default for RequestOptions {
+    fn default() -> Self {
+        Self {
+            max_tokens: Self::default_max_tokens(),
+            stream: Default::default(),
+        }
+    }
+}
+
+impl RequestOptions {
+    const fn default_max_tokens() -> u32 {
+        1000
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Message {
+    pub role: Role,
+    pub content: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Assistant,
+    User,
+    System,
+}
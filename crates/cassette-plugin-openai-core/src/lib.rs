@@ -0,0 +1,2 @@
+#[cfg(feature = "chat")]
+pub mod chat;
@@ -1,18 +1,18 @@
 use cassette_core::{
     cassette::{CassetteContext, GenericCassetteTaskHandle},
-    components::ComponentRenderer,
+    components::{registry::TaskRegistry, ComponentRenderer},
     prelude::*,
     task::{TaskResult, TaskState},
 };
-use cassette_plugin_webcam_core::{hooks::use_webcam, Constraints, Handler};
+use cassette_plugin_webcam_core::{audio::Spec, hooks::use_webcam, Constraints};
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    #[serde(flatten)]
-    handler: Handler,
+pub fn register(registry: &mut TaskRegistry) {
+    ::cassette_plugin_webcam_core::audio::register(registry);
+    registry
+        .insert("WebcamAudio")
+        .with_renderer::<State, Spec>();
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,20 @@
+use cassette_core::components::registry::TaskRegistry;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use yew::Properties;
+
+use crate::Handler;
+
+/// Declare the `WebcamAudio` task kind, without the renderer.
+pub fn register(registry: &mut TaskRegistry) {
+    registry.register::<Spec>("WebcamAudio");
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "ui", derive(Properties))]
+#[serde(rename_all = "camelCase")]
+pub struct Spec {
+    #[serde(flatten)]
+    pub handler: Handler,
+}
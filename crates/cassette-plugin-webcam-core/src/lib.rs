@@ -1,8 +1,11 @@
+#[cfg(feature = "audio")]
+pub mod audio;
 #[cfg(feature = "ui")]
 pub mod hooks;
 #[cfg(feature = "ui")]
 pub mod recorder;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ui")]
 use yew::Properties;
@@ -15,7 +18,7 @@ pub struct Constraints {
     pub video: bool,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[cfg_attr(feature = "ui", derive(Properties))]
 #[serde(rename_all = "camelCase")]
 pub struct Handler {
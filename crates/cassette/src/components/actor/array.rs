@@ -1,34 +1,84 @@
-use cassette_core::{
-    cassette::{CassetteTaskHandle, GenericCassetteTaskHandle},
-    data::actor::{Schema, SchemaArray, SchemaType},
-};
-use patternfly_yew::prelude::FormGroup;
+use cassette_core::data::actor::{array::SchemaSpec, Schema, SchemaPath, SchemaPathItem};
+use patternfly_yew::prelude::{Button, ButtonVariant, Icon};
 use serde_json::Value;
-use yew::{html, Html};
+use yew::{html, Callback, Html, MouseEvent};
 
-pub fn build_form(handle: &CassetteTaskHandle<Value>, spec: SchemaArray, disabled: bool) -> Html {
-    let default = handle.get();
-    let children = spec.0.into_iter().map(|Schema { name, path, ty }| {
-        let default = path.get(default);
-        match ty {
-            SchemaType::Boolean(spec) => {
-                super::boolean::build_form(handle, name, path, spec, default, disabled)
-            }
-            SchemaType::Number(spec) => {
-                super::number::build_form(handle, name, path, spec, default, disabled)
-            }
-            SchemaType::String(spec) => {
-                super::string::build_form(handle, name, path, spec, default, disabled)
-            }
-            SchemaType::Enum(spec) => {
-                super::r#enum::build_form(handle, name, path, spec, default, disabled)
+use super::FormHandles;
+
+pub fn build_form(
+    handles: &FormHandles,
+    name: String,
+    path: SchemaPath,
+    spec: SchemaSpec,
+    default: &Value,
+    disabled: bool,
+) -> Html {
+    let items = match default {
+        Value::Array(items) => items.clone(),
+        _ => spec.default.clone().unwrap_or_default(),
+    };
+
+    let children: Vec<_> = (0..items.len())
+        .map(|index| {
+            let onclick = {
+                let handle = handles.data.clone();
+                let path = path.clone();
+                let items = items.clone();
+                Callback::from(move |_: MouseEvent| {
+                    let mut items = items.clone();
+                    items.remove(index);
+                    handle.set_item(&path, Value::Array(items))
+                })
+            };
+            let schema = Schema {
+                name: format!("#{}", index + 1),
+                path: path.push(SchemaPathItem::List(index)),
+                ty: (*spec.items).clone(),
+                visible_if: None,
+                enabled_if: None,
+                computed_default: None,
+            };
+
+            html! {
+                <div style="display: flex; align-items: flex-start;">
+                    <div style="flex: 1;">
+                        { super::build_field(handles, schema, disabled) }
+                    </div>
+                    <Button
+                        { disabled }
+                        icon={ Icon::Times }
+                        { onclick }
+                        variant={ ButtonVariant::Plain }
+                    />
+                </div>
             }
-        }
-    });
+        })
+        .collect();
+
+    let onclick = {
+        let handle = handles.data.clone();
+        let item = spec.items.default_value();
+        Callback::from(move |_: MouseEvent| {
+            let mut items = items.clone();
+            items.push(item.clone());
+            handle.set_item(&path, Value::Array(items))
+        })
+    };
 
     html! {
-        <FormGroup>
-            { for children }
-        </FormGroup>
+        <div style="margin-bottom: 16px;">
+            { name }
+            <div style="padding-left: 16px; border-left: 2px solid #d2d2d2;">
+                { for children }
+                <Button
+                    { disabled }
+                    icon={ Icon::PlusCircle }
+                    { onclick }
+                    variant={ ButtonVariant::Link }
+                >
+                    { "Add" }
+                </Button>
+            </div>
+        </div>
     }
 }
@@ -0,0 +1,55 @@
+use cassette_core::{
+    cassette::CassetteTaskHandle,
+    data::actor::{
+        date_time::{SchemaDateTimeFormat, SchemaSpec},
+        SchemaPath,
+    },
+};
+use patternfly_yew::prelude::{TextInput, TextInputType};
+use serde_json::Value;
+use yew::{html, Callback, Html};
+
+pub fn build_form(
+    handle: &CassetteTaskHandle<Value>,
+    name: String,
+    path: SchemaPath,
+    spec: SchemaSpec,
+    default: &Value,
+    disabled: bool,
+) -> Html {
+    let id = path.to_string();
+    let onchange = {
+        let handle = handle.clone();
+        Callback::from(move |text: String| {
+            let text = if text.is_empty() {
+                Value::Null
+            } else {
+                Value::String(text)
+            };
+            handle.set_item(&path, text)
+        })
+    };
+
+    let r#type = match spec.format {
+        SchemaDateTimeFormat::Date => TextInputType::Date,
+        SchemaDateTimeFormat::Time => TextInputType::Time,
+        SchemaDateTimeFormat::DateTime => TextInputType::DateTimeLocal,
+    };
+    let value = match default {
+        Value::String(value) => value.clone(),
+        _ => spec.default.unwrap_or_default(),
+    };
+
+    html! {
+        <div style="margin-bottom: 16px;">
+            { name }
+            <TextInput
+                { id }
+                { disabled }
+                { onchange }
+                { r#type }
+                { value }
+            />
+        </div>
+    }
+}
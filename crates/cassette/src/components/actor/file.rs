@@ -0,0 +1,135 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cassette_core::{
+    cassette::CassetteTaskHandle,
+    data::actor::{
+        file::{SchemaFileEncoding, SchemaSpec},
+        SchemaPath,
+    },
+    prelude::*,
+};
+use futures::AsyncReadExt;
+use serde_json::Value;
+use wasm_streams::ReadableStream;
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+
+pub fn build_form(
+    handle: &CassetteTaskHandle<Value>,
+    name: String,
+    path: SchemaPath,
+    spec: SchemaSpec,
+    default: &Value,
+    disabled: bool,
+) -> Html {
+    let size = match default {
+        Value::String(value) => Some(value.len()),
+        _ => None,
+    };
+
+    html! {
+        <FileField
+            { disabled }
+            handle={ handle.clone() }
+            { name }
+            { path }
+            { size }
+            { spec }
+        />
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+struct Props {
+    disabled: bool,
+    handle: CassetteTaskHandle<Value>,
+    name: String,
+    path: SchemaPath,
+    /// The length of the embedded content, if any
+    size: Option<usize>,
+    spec: SchemaSpec,
+}
+
+#[function_component(FileField)]
+fn file_field(props: &Props) -> Html {
+    let Props {
+        disabled,
+        handle,
+        name,
+        path,
+        size,
+        spec: SchemaSpec {
+            accept, encoding, ..
+        },
+    } = props;
+
+    let error = use_state_eq(|| None::<String>);
+    let loading = use_state_eq(|| false);
+
+    let onchange = {
+        let encoding = *encoding;
+        let error = error.clone();
+        let handle = handle.clone();
+        let loading = loading.clone();
+        let path = path.clone();
+        Callback::from(move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            let Some(file) = input.files().and_then(|files| files.item(0)) else {
+                return;
+            };
+
+            let size = file.size() as usize;
+            let mut stream = ReadableStream::from_raw(file.stream()).into_async_read();
+
+            let error = error.clone();
+            let handle = handle.clone();
+            let loading = loading.clone();
+            let path = path.clone();
+            loading.set(true);
+            spawn_local(async move {
+                let mut data = Vec::with_capacity(size);
+                let content = match stream.read_to_end(&mut data).await {
+                    Ok(_) => match encoding {
+                        SchemaFileEncoding::Text => String::from_utf8(data)
+                            .map_err(|_| "The file is not a text file".to_string()),
+                        SchemaFileEncoding::Base64 => Ok(STANDARD.encode(data)),
+                    },
+                    Err(error) => Err(format!("Failed to read file: {error}")),
+                };
+                loading.set(false);
+                match content {
+                    Ok(content) => {
+                        error.set(None);
+                        handle.set_item(&path, Value::String(content))
+                    }
+                    Err(msg) => error.set(Some(msg)),
+                }
+            })
+        })
+    };
+
+    let status = match (*loading, &*error, size) {
+        (true, _, _) => Some(html! { <p style="color: grey;">{ "Loading..." }</p> }),
+        (false, Some(msg), _) => Some(html! { <Error msg={ msg.clone() } /> }),
+        (false, None, Some(size)) => Some(html! {
+            <p style="color: grey;">{ format!("{size} bytes embedded") }</p>
+        }),
+        (false, None, None) => None,
+    };
+
+    html! {
+        <div style="margin-bottom: 16px;">
+            { name }
+            <div>
+                <input
+                    id={ path.to_string() }
+                    type="file"
+                    accept={ accept.clone() }
+                    disabled={ *disabled || *loading }
+                    multiple=false
+                    { onchange }
+                />
+            </div>
+            { status }
+        </div>
+    }
+}
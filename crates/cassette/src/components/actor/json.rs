@@ -0,0 +1,52 @@
+use cassette_core::{
+    cassette::CassetteTaskHandle,
+    data::actor::{json::SchemaSpec, SchemaPath},
+};
+use patternfly_yew::prelude::{ResizeOrientation, TextArea};
+use serde_json::Value;
+use yew::{html, Callback, Html};
+
+pub fn build_form(
+    handle: &CassetteTaskHandle<Value>,
+    name: String,
+    path: SchemaPath,
+    spec: SchemaSpec,
+    default: &Value,
+    disabled: bool,
+) -> Html {
+    let id = path.to_string();
+    let onchange = {
+        let handle = handle.clone();
+        Callback::from(move |text: String| {
+            // NOTE: the incomplete texts are kept until they become valid
+            if let Ok(json) = ::serde_json::from_str(&text) {
+                handle.set_item(&path, json)
+            }
+        })
+    };
+    let placeholder = "{}";
+
+    let value = match default {
+        Value::Null => spec.default.map(Value::Object).unwrap_or(Value::Null),
+        value => value.clone(),
+    };
+    let value = match value {
+        Value::Null => String::default(),
+        value => ::serde_json::to_string_pretty(&value).unwrap_or_default(),
+    };
+
+    html! {
+        <div style="margin-bottom: 16px;">
+            { name }
+            <TextArea
+                { id }
+                { disabled }
+                { onchange }
+                { placeholder }
+                required={ spec.required }
+                resize={ ResizeOrientation::Vertical }
+                { value }
+            />
+        </div>
+    }
+}
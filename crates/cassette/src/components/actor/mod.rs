@@ -1,104 +1,56 @@
 mod array;
 mod boolean;
+mod date_time;
 mod r#enum;
+mod file;
 mod generic;
+mod json;
+mod multi_enum;
 mod number;
+mod object;
 mod root;
+mod secret;
 mod string;
 
-use std::{borrow::Cow, rc::Rc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
 
 use cassette_core::{
     cassette::{CassetteContext, CassetteTaskHandle, GenericCassetteTaskHandle},
-    components::ComponentRenderer,
+    components::{builtin::actor::Spec, ComponentRenderer},
     data::{
-        actor::{SchemaActor, SchemaArray},
+        actor::{Schema, SchemaActor, SchemaArray, SchemaPath, SchemaType},
         table::DataTable,
     },
+    expr::{Expr, ExprContext, Template},
     net::{
         fetch::{Body, FetchRequest, FetchRequestWithoutBody, FetchState, Method},
-        gateway::get_gateway,
+        gateway::{get_gateway, get_namespace},
     },
     prelude::*,
     task::{TaskResult, TaskState},
 };
 use patternfly_yew::prelude::*;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use yew::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    #[serde(default)]
-    pub base_url: Option<String>,
-    pub uri: String,
-    #[serde(default)]
-    pub schema: Rc<SchemaActor>,
-    #[serde(default)]
-    pub default: Value,
-    #[serde(default)]
-    pub table: Option<Rc<DataTable>>,
-
-    #[serde(default = "Spec::default_primary_key")]
-    pub primary_key: String,
-
-    #[serde(default = "Spec::default_label_create")]
-    pub label_create: String,
-    #[serde(default = "Spec::default_label_delete")]
-    pub label_delete: String,
-    #[serde(default = "Spec::default_label_update")]
-    pub label_update: String,
-
-    #[serde(default = "Spec::default_create")]
-    pub create: bool,
-
-    #[serde(default = "Spec::default_delete")]
-    pub delete: bool,
-
-    #[serde(default = "Spec::default_update")]
-    pub update: bool,
-}
-
-impl Spec {
-    fn default_primary_key() -> String {
-        "id".into()
-    }
-
-    fn default_label_create() -> String {
-        "Create".into()
-    }
-
-    fn default_label_delete() -> String {
-        "Delete".into()
-    }
-
-    fn default_label_update() -> String {
-        "Update".into()
-    }
-
-    const fn default_create() -> bool {
-        true
-    }
-
-    const fn default_delete() -> bool {
-        true
-    }
-
-    const fn default_update() -> bool {
-        true
-    }
-}
-
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct State {}
 
 impl ComponentRenderer<Spec> for State {
+    // NOTE: the schema may refer to the in-progress form value, so it is evaluated per field
+    const RAW_KEYS: &'static [&'static str] = &["schema"];
+
     fn render(self, ctx: &mut CassetteContext, spec: Spec) -> TaskResult<Option<Self>> {
         let Spec {
             base_url,
             uri,
+            query,
             schema,
             default,
             table,
@@ -113,7 +65,7 @@ impl ComponentRenderer<Spec> for State {
 
         let force_init = false;
 
-        let content = match use_fetch_actor(ctx, base_url.clone(), &uri, force_init).get() {
+        let content = match use_fetch_actor(ctx, base_url.clone(), &uri, &query, force_init).get() {
             FetchState::Pending | FetchState::Fetching => {
                 return Ok(TaskState::Break {
                     body: html! { <Loading /> },
@@ -297,6 +249,10 @@ fn build_form(ctx: FormContext) -> Html {
 
     let handler_name_data = format!("{handler_name_prefix} data");
     let handle_data = ctx.use_state(handler_name_data, force_init, || default.clone());
+    let handler_name_secrets = format!("{handler_name_prefix} secrets");
+    let handle_secrets = ctx.use_state(handler_name_secrets, force_init, BTreeMap::default);
+    let handler_name_computed = format!("{handler_name_prefix} computed");
+    let handle_computed = ctx.use_state(handler_name_computed, force_init, BTreeMap::default);
     let disabled = matches!(
         submit_state.get(),
         FetchState::Fetching | FetchState::Collecting(_)
@@ -310,13 +266,30 @@ fn build_form(ctx: FormContext) -> Html {
         (Some(schema), None) | (None, Some(schema)) => Some(schema.clone()),
         (None, None) => None,
     };
-    let form_data = self::root::build_form(&handle_data, schema, disabled);
+
+    let mut handles = FormHandles {
+        data: handle_data,
+        secrets: handle_secrets,
+        computed: handle_computed,
+        errors: Rc::default(),
+        hidden: Rc::default(),
+        readonly: Rc::default(),
+    };
+    if let Some(schema) = &schema {
+        let mut errors = eval_fields(ctx, schema, &mut handles);
+        let hidden = handles.hidden.clone();
+        let is_visible = |path: &SchemaPath| !hidden.contains(&path.to_string());
+        errors.extend(schema.validate(&handles.to_body(), &is_visible));
+        handles.errors = Rc::new(errors);
+    }
+    let is_valid = handles.errors.is_empty();
+    let form_data = self::root::build_form(&handles, schema, disabled);
 
     let onclick = {
         let base_url = base_url.cloned();
         let uri = uri.clone();
         let submit_state = submit_state.clone();
-        let handle_data = handle_data.clone();
+        let handles = handles.clone();
         Callback::from(move |_: MouseEvent| {
             let base_url = base_url.clone();
             let handler_name = handler_name_submit.clone();
@@ -328,7 +301,7 @@ fn build_form(ctx: FormContext) -> Html {
                 method: if is_post { Method::POST } else { Method::PUT },
                 name: Cow::Owned(handler_name),
                 uri,
-                body: Some(Body::Json(handle_data.get().clone())),
+                body: Some(Body::Json(handles.to_body())),
             };
 
             request.try_fetch_force(&base_url, state)
@@ -383,7 +356,7 @@ fn build_form(ctx: FormContext) -> Html {
             </StackItem>
             <StackItem>
                 <Button
-                    { disabled }
+                    disabled={ disabled || !is_valid }
                     { onclick }
                     variant={ button_variant }
                 >
@@ -395,6 +368,210 @@ fn build_form(ctx: FormContext) -> Html {
     }
 }
 
+#[derive(Clone)]
+struct FormHandles {
+    data: CassetteTaskHandle<Value>,
+    /// The secrets by their paths, which are merged into the data only when submitted
+    secrets: CassetteTaskHandle<BTreeMap<String, String>>,
+    /// The last computed defaults by their paths, which tell whether the user has edited the fields
+    computed: CassetteTaskHandle<BTreeMap<String, Value>>,
+    /// The error messages by the paths of the invalid fields
+    errors: Rc<BTreeMap<String, String>>,
+    /// The paths of the fields hidden by `visibleIf`, which are not submitted
+    hidden: Rc<BTreeSet<String>>,
+    /// The paths of the fields disabled by `enabledIf`
+    readonly: Rc<BTreeSet<String>>,
+}
+
+impl FormHandles {
+    fn to_body(&self) -> Value {
+        let mut body = self.data.get().clone();
+        for (path, secret) in self.secrets.get().iter() {
+            if let Ok(path) = path.parse::<SchemaPath>() {
+                path.set(&mut body, Value::String(secret.clone()))
+            }
+        }
+        for path in self.hidden.iter() {
+            if let Ok(path) = path.parse::<SchemaPath>() {
+                path.remove(&mut body);
+            }
+        }
+        body
+    }
+}
+
+/// Evaluate the expressions of the fields against the in-progress form value,
+/// returning the error messages by the paths of the fields.
+///
+/// The fields untouched by the user follow their computed defaults.
+fn eval_fields(
+    ctx: &CassetteContext,
+    schema: &SchemaArray,
+    handles: &mut FormHandles,
+) -> BTreeMap<String, String> {
+    let data = handles.data.get().clone();
+    let form_ctx = FormExprContext {
+        ctx,
+        vars: json!({
+            "form": data,
+            "user": {
+                "namespace": get_namespace(),
+            },
+        }),
+    };
+    let eval_bool = |expr: &Option<String>| match expr {
+        Some(expr) => expr
+            .parse::<Expr>()
+            .and_then(|expr| expr.eval_bool(&form_ctx))
+            .map_err(|error| format!("Failed to evaluate expression: {error}")),
+        None => Ok(true),
+    };
+
+    let computed = handles.computed.get().clone();
+    let mut updated = data.clone();
+    let mut updated_computed = computed.clone();
+    let mut errors = BTreeMap::default();
+    let mut hidden = BTreeSet::default();
+    let mut readonly = BTreeSet::default();
+    for (path, field) in schema.fields(&data) {
+        let key = path.to_string();
+        match eval_bool(&field.visible_if) {
+            Ok(true) => (),
+            Ok(false) => {
+                hidden.insert(key);
+                continue;
+            }
+            Err(error) => {
+                errors.insert(key.clone(), error);
+            }
+        }
+        match eval_bool(&field.enabled_if) {
+            Ok(true) => (),
+            Ok(false) => {
+                readonly.insert(key.clone());
+            }
+            Err(error) => {
+                errors.insert(key.clone(), error);
+            }
+        }
+
+        if let Some(template) = &field.computed_default {
+            // NOTE: the field is untouched if it still has the last computed value,
+            //       so the edited or cleared fields are kept as they are
+            let value = path.get(&updated);
+            let is_untouched = match computed.get(&key) {
+                Some(last) => value == last,
+                None => value.is_null(),
+            };
+            if is_untouched {
+                match template
+                    .parse::<Template>()
+                    .and_then(|template| template.eval(&form_ctx))
+                {
+                    Ok(value) => {
+                        path.set(&mut updated, value.clone());
+                        updated_computed.insert(key, value);
+                    }
+                    Err(error) => {
+                        errors.insert(key, format!("Failed to compute the default: {error}"));
+                    }
+                }
+            }
+        }
+    }
+
+    if updated_computed != computed {
+        handles.computed.clone().lazy().set(updated_computed);
+    }
+    if updated != data {
+        handles.data.set(updated);
+    }
+    handles.hidden = Rc::new(hidden);
+    handles.readonly = Rc::new(readonly);
+    errors
+}
+
+/// Resolves `~/form/...` to the in-progress form value and `~/user/...` to the current user,
+/// falling back to the task spec.
+struct FormExprContext<'a, C> {
+    ctx: &'a C,
+    vars: Value,
+}
+
+impl<C> ExprContext for FormExprContext<'_, C>
+where
+    C: ExprContext,
+{
+    fn get_data(&self, key: &str) -> Result<Value, String> {
+        self.ctx.get_data(key)
+    }
+
+    fn get_spec(&self, key: &str) -> Result<Value, String> {
+        match self.vars.pointer(key) {
+            Some(value) => Ok(value.clone()),
+            None => self.ctx.get_spec(key),
+        }
+    }
+}
+
+fn build_field(handles: &FormHandles, schema: Schema, disabled: bool) -> Html {
+    let Schema { name, path, ty, .. } = schema;
+    if handles.hidden.contains(&path.to_string()) {
+        return Html::default();
+    }
+    let disabled = disabled || handles.readonly.contains(&path.to_string());
+    let handle = &handles.data;
+    let default = path.get(handle.get());
+
+    let error = handles.errors.get(&path.to_string()).map(|msg| {
+        html! {
+            <p style="color: var(--pf-v5-global--danger-color--100); margin-top: -12px; margin-bottom: 16px;">
+                { msg.clone() }
+            </p>
+        }
+    });
+
+    let field = match ty {
+        SchemaType::Boolean(spec) => {
+            self::boolean::build_form(handle, name, path, spec, default, disabled)
+        }
+        SchemaType::Number(spec) => {
+            self::number::build_form(handle, name, path, spec, default, disabled)
+        }
+        SchemaType::String(spec) => {
+            self::string::build_form(handle, name, path, spec, default, disabled)
+        }
+        SchemaType::Enum(spec) => {
+            self::r#enum::build_form(handle, name, path, spec, default, disabled)
+        }
+        SchemaType::Array(spec) => {
+            self::array::build_form(handles, name, path, spec, default, disabled)
+        }
+        SchemaType::Object(spec) => self::object::build_form(handles, name, path, spec, disabled),
+        SchemaType::DateTime(spec) => {
+            self::date_time::build_form(handle, name, path, spec, default, disabled)
+        }
+        // NOTE: the secrets are validated with the submitted body
+        SchemaType::Secret(_) => self::secret::build_form(&handles.secrets, name, path, disabled),
+        SchemaType::MultiEnum(spec) => {
+            self::multi_enum::build_form(handle, name, path, spec, default, disabled)
+        }
+        SchemaType::File(spec) => {
+            self::file::build_form(handle, name, path, spec, default, disabled)
+        }
+        SchemaType::Json(spec) => {
+            self::json::build_form(handle, name, path, spec, default, disabled)
+        }
+    };
+
+    html! {
+        <>
+            { field }
+            { error }
+        </>
+    }
+}
+
 struct FormDeleteContext<'a, 'b> {
     ctx: &'a mut CassetteContext<'b>,
     base_url: Option<&'a String>,
@@ -522,6 +699,7 @@ fn use_fetch_actor(
     ctx: &mut CassetteContext,
     base_url: Option<String>,
     uri: &str,
+    query: &BTreeMap<String, String>,
     force: bool,
 ) -> CassetteTaskHandle<FetchState<SchemaActor>> {
     let handler_name = "fetch";
@@ -532,7 +710,14 @@ fn use_fetch_actor(
         let request = FetchRequestWithoutBody {
             method: Method::GET,
             name: Cow::Borrowed(handler_name),
-            uri: format!("{uri}/_actor"),
+            uri: if query.is_empty() {
+                format!("{uri}/_actor")
+            } else {
+                let query = ::url::form_urlencoded::Serializer::new(String::default())
+                    .extend_pairs(query)
+                    .finish();
+                format!("{uri}/_actor?{query}")
+            },
             body: None,
         };
 
@@ -0,0 +1,74 @@
+use cassette_core::{
+    cassette::CassetteTaskHandle,
+    data::actor::{multi_enum::SchemaSpec, SchemaPath},
+};
+use patternfly_yew::prelude::{Checkbox, CheckboxState};
+use serde_json::Value;
+use yew::{html, Callback, Html};
+
+pub fn build_form(
+    handle: &CassetteTaskHandle<Value>,
+    name: String,
+    path: SchemaPath,
+    spec: SchemaSpec,
+    default: &Value,
+    disabled: bool,
+) -> Html {
+    let selected: Vec<String> = match default {
+        Value::Array(values) => values
+            .iter()
+            .filter_map(|value| value.as_str().map(Into::into))
+            .collect(),
+        _ => spec.default,
+    };
+
+    let choices = spec.choices.iter().map(|choice| {
+        let id = format!("{path}/{choice}");
+        let checked = if selected.contains(choice) {
+            CheckboxState::Checked
+        } else {
+            CheckboxState::Unchecked
+        };
+        let onchange = {
+            let choice = choice.clone();
+            let choices = spec.choices.clone();
+            let handle = handle.clone();
+            let path = path.clone();
+            let selected = selected.clone();
+            Callback::from(move |state: CheckboxState| {
+                let is_checked: bool = state.into();
+                // NOTE: the selected values are kept in the order of the choices
+                let updated = choices
+                    .iter()
+                    .filter(|&value| {
+                        if *value == choice {
+                            is_checked
+                        } else {
+                            selected.contains(value)
+                        }
+                    })
+                    .cloned()
+                    .map(Value::String)
+                    .collect();
+                handle.set_item(&path, Value::Array(updated))
+            })
+        };
+
+        html! {
+            <Checkbox
+                { id }
+                { checked }
+                { disabled }
+                label={ choice.clone() }
+                { onchange }
+            />
+        }
+    });
+
+    html! {
+        <div style="margin-bottom: 16px;">
+            { name }
+            { for choices }
+        </div>
+    }
+}
@@ -0,0 +1,44 @@
+use cassette_core::data::actor::{object::SchemaSpec, Schema, SchemaArray, SchemaPath};
+use patternfly_yew::prelude::FormGroup;
+use yew::{html, Html};
+
+use super::FormHandles;
+
+pub fn build_form(
+    handles: &FormHandles,
+    name: String,
+    path: SchemaPath,
+    spec: SchemaSpec,
+    disabled: bool,
+) -> Html {
+    html! {
+        <div style="margin-bottom: 16px;">
+            { name }
+            <div style="padding-left: 16px; border-left: 2px solid #d2d2d2;">
+                { build_fields(handles, &path, spec.fields, disabled) }
+            </div>
+        </div>
+    }
+}
+
+/// Build the fields, whose paths are relative to the given one.
+pub fn build_fields(
+    handles: &FormHandles,
+    path: &SchemaPath,
+    spec: SchemaArray,
+    disabled: bool,
+) -> Html {
+    let children = spec.0.into_iter().map(|schema| {
+        let schema = Schema {
+            path: path.join(&schema.path),
+            ..schema
+        };
+        super::build_field(handles, schema, disabled)
+    });
+
+    html! {
+        <FormGroup>
+            { for children }
+        </FormGroup>
+    }
+}
@@ -1,20 +1,12 @@
-use cassette_core::{
-    cassette::CassetteTaskHandle,
-    data::actor::{SchemaArray, SchemaPath},
-};
-use serde_json::Value;
+use cassette_core::data::actor::{SchemaArray, SchemaPath};
 use yew::Html;
 
-pub fn build_form(
-    handle: &CassetteTaskHandle<Value>,
-    schema: Option<SchemaArray>,
-    disabled: bool,
-) -> Html {
+use super::FormHandles;
+
+pub fn build_form(handles: &FormHandles, schema: Option<SchemaArray>, disabled: bool) -> Html {
+    let path = SchemaPath::default();
     match schema {
-        Some(spec) => super::array::build_form(handle, spec, disabled),
-        None => {
-            let path = SchemaPath::default();
-            super::generic::build_form(handle, path, disabled)
-        }
+        Some(spec) => super::object::build_fields(handles, &path, spec, disabled),
+        None => super::generic::build_form(&handles.data, path, disabled),
     }
 }
@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use cassette_core::{
+    cassette::{CassetteTaskHandle, GenericCassetteTaskHandle},
+    data::actor::SchemaPath,
+};
+use patternfly_yew::prelude::{TextInput, TextInputType};
+use yew::{html, Callback, Html};
+
+pub fn build_form(
+    secrets: &CassetteTaskHandle<BTreeMap<String, String>>,
+    name: String,
+    path: SchemaPath,
+    disabled: bool,
+) -> Html {
+    let id = path.to_string();
+    let onchange = {
+        let id = id.clone();
+        let secrets = secrets.clone();
+        Callback::from(move |text: String| {
+            let mut updated = secrets.get().clone();
+            if text.is_empty() {
+                updated.remove(&id);
+            } else {
+                updated.insert(id.clone(), text);
+            }
+            secrets.set(updated)
+        })
+    };
+
+    let value = secrets.get().get(&id).cloned().unwrap_or_default();
+
+    html! {
+        <div style="margin-bottom: 16px;">
+            { name }
+            <TextInput
+                { id }
+                { disabled }
+                { onchange }
+                r#type={ TextInputType::Password }
+                { value }
+            />
+        </div>
+    }
+}
@@ -0,0 +1,109 @@
+use cassette_core::{
+    cassette::CassetteContext,
+    components::{builtin::export::Spec, ComponentRenderer},
+    task::{TaskResult, TaskState},
+};
+use js_sys::{Array, Uint8Array};
+use patternfly_yew::prelude::*;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+use yew::prelude::*;
+
+/// The time to keep the downloaded data in milliseconds, until the browser has read it
+const REVOKE_TIMEOUT_MS: i32 = 60_000;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct State {}
+
+impl ComponentRenderer<Spec> for State {
+    fn render(self, _ctx: &mut CassetteContext, spec: Spec) -> TaskResult<Option<Self>> {
+        let Self {} = self;
+
+        Ok(TaskState::Continue {
+            body: html! { <Inner { spec } /> },
+            state: None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+struct Props {
+    spec: Spec,
+}
+
+#[function_component(Inner)]
+fn inner(props: &Props) -> Html {
+    let Props { spec } = props;
+
+    let error = use_state_eq(|| None);
+
+    let onclick = {
+        let error = error.clone();
+        let spec = spec.clone();
+        Callback::from(move |_: MouseEvent| {
+            // NOTE: the data is serialized only when requested, as it may be large
+            error.set(
+                spec.export()
+                    .map_err(|error| format!("Failed to export data: {error}"))
+                    .and_then(|data| {
+                        download(&spec.filename(), spec.export_type().mime_type(), &data)
+                    })
+                    .err(),
+            )
+        })
+    };
+
+    let error = (*error).clone().map(|msg| {
+        html! {
+            <Alert inline=true title="Error" r#type={ AlertType::Danger }>
+                { msg }
+            </Alert>
+        }
+    });
+
+    html! {
+        <>
+            <Button
+                icon={ Icon::Download }
+                { onclick }
+                variant={ ButtonVariant::Secondary }
+            >
+                { spec.label.clone() }
+            </Button>
+            { error }
+        </>
+    }
+}
+
+/// Let the browser save the data as a file.
+fn download(filename: &str, mime_type: &str, data: &[u8]) -> Result<(), String> {
+    fn download_unchecked(filename: &str, mime_type: &str, data: &[u8]) -> Result<(), JsValue> {
+        let parts = Array::of1(&Uint8Array::from(data));
+        let options = BlobPropertyBag::new();
+        options.set_type(mime_type);
+        let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+
+        let url = Url::create_object_url_with_blob(&blob)?;
+        let anchor: HtmlAnchorElement = ::gloo_utils::document()
+            .create_element("a")?
+            .unchecked_into();
+        anchor.set_href(&url);
+        anchor.set_download(filename);
+        anchor.click();
+
+        // NOTE: the browser may read the data after the click has returned
+        let revoke = Closure::once_into_js(move || {
+            let _ = Url::revoke_object_url(&url);
+        });
+        ::gloo_utils::window().set_timeout_with_callback_and_timeout_and_arguments_0(
+            revoke.unchecked_ref(),
+            REVOKE_TIMEOUT_MS,
+        )?;
+        Ok(())
+    }
+
+    download_unchecked(filename, mime_type, data)
+        .map_err(|error| format!("Failed to download data: {error:?}"))
+}
@@ -3,8 +3,10 @@ use std::rc::Rc;
 use byte_unit::{Byte, UnitType};
 use cassette_core::{
     cassette::{CassetteContext, CassetteTaskHandle, GenericCassetteTaskHandle},
-    components::ComponentRenderer,
-    data::table::{DataTable, DataTableLog, DataTableSourceType},
+    components::{builtin::file_upload::Spec, ComponentRenderer},
+    data::table::{
+        DataTable, DataTableLog, DataTableLogKind, DataTableSourceOptions, DataTableSourceType,
+    },
     net::fetch::FetchState,
     prelude::*,
     task::{TaskResult, TaskState},
@@ -17,29 +19,6 @@ use web_sys::{FileList, HtmlElement, HtmlInputElement};
 use yew::{platform::spawn_local, prelude::*};
 use yew_hooks::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    #[serde(default = "Spec::default_label_title")]
-    label_title: String,
-
-    #[serde(default)]
-    label_detail: Option<String>,
-
-    #[serde(default = "Spec::default_type")]
-    r#type: DataTableSourceType,
-}
-
-impl Spec {
-    fn default_label_title() -> String {
-        "Upload".into()
-    }
-
-    const fn default_type() -> DataTableSourceType {
-        DataTableSourceType::Raw
-    }
-}
-
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct State {
@@ -88,13 +67,24 @@ fn manager(props: &Props) -> Html {
                 label_title,
                 label_detail,
                 r#type,
+                options,
             },
     } = props;
     let r#type = *r#type;
 
-    let label_detail = label_detail
-        .clone()
-        .unwrap_or_else(|| format!("Please upload a .{} file", r#type));
+    let extensions = r#type.extensions();
+    let accept = if extensions.is_empty() {
+        None
+    } else {
+        Some(extensions.join(","))
+    };
+    let label_detail = label_detail.clone().unwrap_or_else(|| {
+        if extensions.is_empty() {
+            "Please upload a file".into()
+        } else {
+            format!("Please upload a {} file", extensions.join(" or "))
+        }
+    });
 
     let node = use_node_ref();
 
@@ -103,8 +93,9 @@ fn manager(props: &Props) -> Html {
         UseDropOptions {
             onfiles: {
                 let drop_content = drop_content.clone();
+                let options = options.clone();
                 Some(Box::new(move |files, _data_transfer| {
-                    drop_content.set_files(r#type, files)
+                    drop_content.set_files(r#type, options.clone(), files)
                 }))
             },
             ..Default::default()
@@ -115,10 +106,11 @@ fn manager(props: &Props) -> Html {
 
     let onchange = {
         let drop_content = drop_content.clone();
+        let options = options.clone();
         Callback::from(move |event: Event| {
             let input: HtmlInputElement = event.target_unchecked_into();
             if let Some(files) = input.files() {
-                drop_content.set_file_list(r#type, files)
+                drop_content.set_file_list(r#type, options.clone(), files)
             }
         })
     };
@@ -132,10 +124,11 @@ fn manager(props: &Props) -> Html {
     };
     let ondrop = {
         let drop_content = drop_content.clone();
+        let options = options.clone();
         Callback::from(move |event: DragEvent| {
             event.prevent_default();
             if let Some(files) = event.data_transfer().and_then(|dt| dt.files()) {
-                drop_content.set_file_list(r#type, files)
+                drop_content.set_file_list(r#type, options.clone(), files)
             }
         })
     };
@@ -185,7 +178,7 @@ fn manager(props: &Props) -> Html {
             <input // placeholder
                 ref={ file_input_ref }
                 type="file"
-                accept="*.csv"
+                { accept }
                 { disabled }
                 multiple=false
                 style="display: none;"
@@ -222,26 +215,37 @@ where
     fn set_file_iter(
         &self,
         r#type: DataTableSourceType,
+        options: DataTableSourceOptions,
         file: Option<::web_sys::File>,
         length: u32,
     ) {
         match length {
             0 => self.set(FetchState::Pending),
             1 => match file {
-                Some(file) => self.set_file_unchecked(r#type, file),
+                Some(file) => self.set_file_unchecked(r#type, options, file),
                 None => self.set(FetchState::Pending),
             },
             2.. => self.set(FetchState::Error("Cannot get multiple items".into())),
         }
     }
 
-    fn set_file_list(&self, r#type: DataTableSourceType, files: FileList) {
+    fn set_file_list(
+        &self,
+        r#type: DataTableSourceType,
+        options: DataTableSourceOptions,
+        files: FileList,
+    ) {
         let length = files.length();
         let file = files.item(0);
-        self.set_file_iter(r#type, file, length)
+        self.set_file_iter(r#type, options, file, length)
     }
 
-    fn set_file_unchecked(&self, r#type: DataTableSourceType, file: ::web_sys::File) {
+    fn set_file_unchecked(
+        &self,
+        r#type: DataTableSourceType,
+        options: DataTableSourceOptions,
+        file: ::web_sys::File,
+    ) {
         self.set(FetchState::Fetching);
 
         let name = file.name();
@@ -252,11 +256,11 @@ where
         spawn_local(async move {
             let mut data = Vec::with_capacity(size);
             state.set(match stream.read_to_end(&mut data).await {
-                Ok(_) => match r#type.parse_bytes(data) {
+                Ok(_) => match r#type.parse_bytes(data, &options) {
                     Ok(data) => FetchState::Completed(Rc::new(DataTable {
                         name,
                         data: Rc::new(data),
-                        log: DataTableLog::default(),
+                        log: DataTableLog::new(DataTableLogKind::Uploaded),
                     })),
                     Err(error) => FetchState::Error(format!("Failed to parse file data: {error}")),
                 },
@@ -265,10 +269,15 @@ where
         })
     }
 
-    fn set_files(&self, r#type: DataTableSourceType, mut files: Vec<::web_sys::File>) {
+    fn set_files(
+        &self,
+        r#type: DataTableSourceType,
+        options: DataTableSourceOptions,
+        mut files: Vec<::web_sys::File>,
+    ) {
         let length = files.len() as u32;
         let file = files.pop();
-        self.set_file_iter(r#type, file, length)
+        self.set_file_iter(r#type, options, file, length)
     }
 }
 
@@ -0,0 +1,63 @@
+use cassette_core::{
+    cassette::CassetteContext,
+    components::builtin::for_each::Spec,
+    task::{TaskPath, TaskResult, TaskSpec, TaskState},
+};
+use serde_json::{Map, Value};
+use yew::prelude::*;
+
+use super::RootCassetteTasks;
+
+pub fn render(ctx: CassetteContext, spec: &TaskSpec) -> TaskResult<()> {
+    // NOTE: the nested tasks are evaluated per item, so only `items` is evaluated here
+    let Spec { items, tasks } = ::serde_json::from_value(spec.0.clone())
+        .map_err(|error| format!("Failed to parse task spec: {error}"))?;
+
+    let items = match ctx.eval(&items)? {
+        Value::Null => Vec::default(),
+        Value::Array(items) => items,
+        _ => return Err("ForEach items should be an array".into()),
+    };
+
+    let mut contents = vec![];
+    let mut is_break = false;
+    for (index, item) in items.iter().enumerate() {
+        let vars = {
+            let mut map = Map::default();
+            map.insert("index".into(), index.into());
+            map.insert("item".into(), item.clone());
+            map
+        };
+        let path = TaskPath::default().key("items").index(index);
+        let mut state = ctx.scoped(&path, &tasks, vars);
+
+        let (bodies, broken) = RootCassetteTasks(&tasks).render(&mut state);
+        contents.extend(bodies);
+        if broken {
+            is_break = true;
+            break;
+        }
+    }
+
+    // Drop the states of the removed items
+    let mut state: TaskSpec = ctx.get_child()?.unwrap_or_default();
+    if !state.0.is_object() {
+        state = TaskSpec::default();
+    }
+    if let Value::Object(map) = &mut state.0 {
+        match map.get_mut("items") {
+            Some(Value::Array(states)) => states.truncate(items.len()),
+            _ => {
+                map.insert("items".into(), Value::Array(Vec::default()));
+            }
+        }
+    }
+
+    let body = html! { <>{ for contents }</> };
+    ctx.set_task_state(state);
+    if is_break {
+        Ok(TaskState::Break { body, state: () })
+    } else {
+        Ok(TaskState::Continue { body, state: () })
+    }
+}
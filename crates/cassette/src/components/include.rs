@@ -0,0 +1,56 @@
+use cassette_core::{
+    cassette::CassetteContext,
+    components::builtin::include::Spec,
+    task::{TaskPath, TaskResult, TaskSpec, TaskState},
+};
+use serde_json::{Map, Value};
+use yew::prelude::*;
+
+use super::RootCassetteTasks;
+
+pub fn render(ctx: CassetteContext, spec: &TaskSpec) -> TaskResult<()> {
+    // NOTE: the included tasks are evaluated in their own scope, so only `inputs` is evaluated here
+    let Spec {
+        component,
+        inputs,
+        outputs,
+        tasks,
+    } = ::serde_json::from_value(spec.0.clone())
+        .map_err(|error| format!("Failed to parse task spec: {error}"))?;
+
+    let tasks = tasks.ok_or_else(|| {
+        format!("Failed to include component {component:?}: no such component or cyclic include")
+    })?;
+
+    let vars = {
+        let mut map = Map::default();
+        map.insert("input".into(), ctx.eval(&Value::Object(inputs))?);
+        map
+    };
+    let mut state = ctx.scoped(&TaskPath::default().key("tasks"), &tasks, vars);
+
+    let (contents, is_break) = RootCassetteTasks(&tasks).render(&mut state);
+
+    // Export the outputs, which can refer to the included tasks
+    let outputs = if is_break {
+        Value::Null
+    } else {
+        CassetteContext::new(&mut state, ctx.task()).eval(&Value::Object(outputs))?
+    };
+
+    let mut state: TaskSpec = ctx.get_child()?.unwrap_or_default();
+    if !state.0.is_object() {
+        state = TaskSpec::default();
+    }
+    if let Value::Object(map) = &mut state.0 {
+        map.insert("outputs".into(), outputs);
+    }
+
+    let body = html! { <>{ for contents }</> };
+    ctx.set_task_state(state);
+    if is_break {
+        Ok(TaskState::Break { body, state: () })
+    } else {
+        Ok(TaskState::Continue { body, state: () })
+    }
+}
@@ -0,0 +1,30 @@
+use std::rc::Rc;
+
+use cassette_core::{
+    cassette::CassetteContext,
+    components::{builtin::join::Spec, ComponentRenderer},
+    data::table::DataTable,
+    task::{TaskResult, TaskState},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct State {
+    #[serde(default, flatten)]
+    table: Option<Rc<DataTable>>,
+}
+
+impl ComponentRenderer<Spec> for State {
+    fn render(self, _ctx: &mut CassetteContext, spec: Spec) -> TaskResult<Option<Self>> {
+        let table = spec
+            .apply(self.table.as_deref())
+            .map_err(|error| format!("Failed to combine the tables: {error}"))?;
+
+        Ok(TaskState::Skip {
+            state: Some(Self {
+                table: Some(Rc::new(table)),
+            }),
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use cassette_core::task::CassetteTaskColumnType;
+use yew::prelude::*;
+
+/// Arrange the task bodies into rows of columns.
+#[derive(Default)]
+pub struct TaskLayout {
+    rows: Vec<Row>,
+}
+
+enum Row {
+    All(Html),
+    Columns(Vec<Vec<Html>>),
+}
+
+impl TaskLayout {
+    pub fn push(&mut self, column: CassetteTaskColumnType, body: Html) {
+        match (column, self.rows.last_mut()) {
+            (CassetteTaskColumnType::All, _) => self.rows.push(Row::All(body)),
+            (CassetteTaskColumnType::Current, Some(Row::Columns(columns))) => {
+                match columns.last_mut() {
+                    Some(column) => column.push(body),
+                    None => columns.push(vec![body]),
+                }
+            }
+            (CassetteTaskColumnType::New, Some(Row::Columns(columns))) => columns.push(vec![body]),
+            (
+                CassetteTaskColumnType::Current | CassetteTaskColumnType::New,
+                Some(Row::All(_)) | None,
+            ) => self.rows.push(Row::Columns(vec![vec![body]])),
+        }
+    }
+
+    pub fn build(self) -> Vec<Html> {
+        self.rows
+            .into_iter()
+            .flat_map(|row| match row {
+                Row::All(body) => vec![body],
+                // NOTE: a single column is stacked as-is
+                Row::Columns(mut columns) if columns.len() == 1 => columns.pop().unwrap(),
+                Row::Columns(columns) => {
+                    let style = format!("--cassette-grid-columns: {};", columns.len());
+                    vec![html! {
+                        <div class="cassette-grid" { style }>
+                            { for columns.into_iter().map(|bodies| html! {
+                                <div class="cassette-grid-column">
+                                    { for bodies }
+                                </div>
+                            }) }
+                        </div>
+                    }]
+                }
+            })
+            .collect()
+    }
+}
@@ -8,24 +8,16 @@ use cassette_core::net::gateway::get_gateway;
 use cassette_core::prelude::*;
 use cassette_core::{
     cassette::CassetteContext,
-    components::ComponentRenderer,
-    data::table::DataTable,
+    components::{builtin::load::Spec, ComponentRenderer},
+    data::table::{
+        DataTable, DataTableLog, DataTableLogKind, DataTableSourceOptions, DataTableSourceType,
+    },
     net::fetch::FetchState,
     task::{TaskResult, TaskState},
 };
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    #[serde(default)]
-    base_url: Option<String>,
-    uri: String,
-    #[serde(default)]
-    query: BTreeMap<String, String>,
-}
-
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct State {
@@ -39,11 +31,13 @@ impl ComponentRenderer<Spec> for State {
             base_url,
             uri,
             query,
+            r#type,
+            options,
         } = spec;
 
         let force_init = false;
 
-        match use_fetch(ctx, base_url, uri, query, force_init).get() {
+        match use_fetch(ctx, base_url, uri, query, r#type, options, force_init).get() {
             FetchState::Pending | FetchState::Fetching => Ok(TaskState::Break {
                 body: html! { <Loading /> },
                 state: Some(Self { data: None }),
@@ -68,6 +62,8 @@ fn use_fetch(
     base_url: Option<String>,
     mut uri: String,
     query: BTreeMap<String, String>,
+    r#type: Option<DataTableSourceType>,
+    options: DataTableSourceOptions,
     force: bool,
 ) -> CassetteTaskHandle<FetchState<DataTable>> {
     let handler_name = "fetch";
@@ -75,6 +71,7 @@ fn use_fetch(
     {
         let state = state.clone();
         let base_url = base_url.unwrap_or(get_gateway());
+        let name = uri.rsplit('/').next().unwrap_or_default().to_string();
         let request = FetchRequestWithoutBody {
             method: Method::GET,
             name: Cow::Borrowed(handler_name),
@@ -95,7 +92,19 @@ fn use_fetch(
             body: None,
         };
 
-        request.try_fetch(&base_url, state)
+        match r#type {
+            Some(r#type) => request.try_fetch_bytes(&base_url, state, move |bytes| {
+                match r#type.parse_bytes(bytes, &options) {
+                    Ok(data) => FetchState::Completed(Rc::new(DataTable {
+                        name,
+                        data: Rc::new(data),
+                        log: DataTableLog::new(DataTableLogKind::Loaded),
+                    })),
+                    Err(error) => FetchState::Error(format!("Failed to parse data: {error}")),
+                }
+            }),
+            None => request.try_fetch(&base_url, state),
+        }
     }
     state
 }
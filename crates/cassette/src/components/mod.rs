@@ -1,16 +1,114 @@
 mod actor;
+mod export;
 mod file_upload;
+mod for_each;
+mod include;
+mod join;
+mod layout;
 mod load;
 mod table;
 mod text;
 mod text_input;
+mod transform;
 mod variable;
 
 use cassette_core::{
-    cassette::{CassetteContext, CassetteState},
-    components::ComponentRendererExt,
-    task::{CassetteTask, TaskRenderer, TaskResult},
+    cassette::CassetteState,
+    components::{builtin, registry::TaskRegistry},
+    task::{CassetteTask, TaskRenderer, TaskResult, TaskState},
 };
+use patternfly_yew::prelude::*;
+use yew::prelude::*;
+
+/// Build a registry with the built-in task kinds and the enabled plugins, with their renderers.
+pub fn registry() -> TaskRegistry {
+    let mut registry = builtin::registry();
+    registry
+        .insert("Actor")
+        .with_renderer::<self::actor::State, builtin::actor::Spec>();
+    registry
+        .insert("Export")
+        .with_renderer::<self::export::State, builtin::export::Spec>();
+    registry
+        .insert("FileUpload")
+        .with_renderer::<self::file_upload::State, builtin::file_upload::Spec>();
+    registry
+        .insert("ForEach")
+        .with_render(self::for_each::render);
+    registry
+        .insert("Include")
+        .with_render(self::include::render);
+    registry
+        .insert("Join")
+        .with_renderer::<self::join::State, builtin::join::Spec>();
+    registry
+        .insert("Load")
+        .with_renderer::<self::load::State, builtin::load::Spec>();
+    registry.insert("Table").with_render(self::table::render);
+    registry
+        .insert("Text")
+        .with_renderer::<self::text::State, builtin::text::Spec>();
+    registry
+        .insert("TextInput")
+        .with_renderer::<self::text_input::State, builtin::text_input::Spec>();
+    registry
+        .insert("Transform")
+        .with_render(self::transform::render);
+    registry
+        .insert("Variable")
+        .with_render(self::variable::render);
+
+    #[cfg(feature = "kubernetes-list")]
+    ::cassette_plugin_kubernetes_list::register(&mut registry);
+    #[cfg(feature = "openai-chat")]
+    ::cassette_plugin_openai_chat::register(&mut registry);
+    #[cfg(feature = "webcam-audio")]
+    ::cassette_plugin_webcam_audio::register(&mut registry);
+    registry
+}
+
+pub struct RootCassetteTasks<'a>(pub(crate) &'a [CassetteTask]);
+
+impl RootCassetteTasks<'_> {
+    /// Render the tasks in order, returning the laid-out rows and whether the flow is broken.
+    pub fn render(&self, state: &mut CassetteState) -> (Vec<Html>, bool) {
+        let Self { 0: tasks } = self;
+
+        let mut layout = self::layout::TaskLayout::default();
+        for task in tasks.iter() {
+            let column = task.metadata.column;
+            match RootCassetteTask(task).render(state) {
+                Ok(TaskState::Break { body, state: _ }) => {
+                    layout.push(column, body);
+                    return (layout.build(), true);
+                }
+                Ok(TaskState::Continue { body, state: _ }) => {
+                    layout.push(column, body);
+                    continue;
+                }
+                Ok(TaskState::Skip { state: _ }) => {
+                    continue;
+                }
+                Err(error) => {
+                    let body = html! {
+                        <Alert
+                            inline=true
+                            title="Error"
+                            r#type={AlertType::Danger}
+                        >
+                            <p style="white-space: pre-line;">
+                                { error }
+                            </p>
+                        </Alert>
+                    };
+                    layout.push(column, body);
+                    return (layout.build(), true);
+                }
+            }
+        }
+        (layout.build(), false)
+    }
+}
 
 pub struct RootCassetteTask<'a>(pub(crate) &'a CassetteTask);
 
@@ -18,29 +116,6 @@ impl TaskRenderer for RootCassetteTask<'_> {
     fn render(&self, state: &mut CassetteState) -> TaskResult<()> {
         let Self { 0: task } = self;
 
-        let CassetteTask {
-            name,
-            kind,
-            metadata: _,
-            spec,
-        } = task;
-
-        let ctx = CassetteContext::new(state, task);
-
-        match kind.as_str() {
-            "Actor" => self::actor::State::render_with(ctx, spec),
-            "FileUpload" => self::file_upload::State::render_with(ctx, spec),
-            #[cfg(feature = "kubernetes-list")]
-            "KubernetesList" => ::cassette_plugin_kubernetes_list::State::render_with(ctx, spec),
-            "Load" => self::load::State::render_with(ctx, spec),
-            #[cfg(feature = "openai-chat")]
-            "OpenAIChat" => ::cassette_plugin_openai_chat::State::render_with(ctx, spec),
-            "Table" => self::table::State::render_with(ctx, spec),
-            "Text" => self::text::State::render_with(ctx, spec),
-            "TextInput" => self::text_input::State::render_with(ctx, spec),
-            "Variable" => self::variable::render(ctx, spec),
-            "WebcamAudio" => ::cassette_plugin_webcam_audio::State::render_with(ctx, spec),
-            _ => Err(format!("Unknown type: {name:?} as {kind}")),
-        }
+        TaskRegistry::current().render(state, task)
     }
 }
@@ -0,0 +1,205 @@
+use byte_unit::{Byte, UnitType};
+use cassette_core::{
+    components::builtin::table::{TableCellColor, TableCellRenderer},
+    data::{
+        column::{parse_bool, parse_datetime},
+        view::{cell_number, cell_text},
+    },
+};
+use chrono::{DateTime, Utc};
+use patternfly_yew::prelude::*;
+use serde_json::Value;
+use yew::prelude::*;
+
+/// Render the cell with the given renderer, falling back to the plain text
+/// if the value cannot be rendered so.
+pub(super) fn render(
+    renderer: &TableCellRenderer,
+    value: &Value,
+    columns: &[String],
+    row: &[Value],
+) -> Html {
+    if value.is_null() {
+        return Html::default();
+    }
+
+    let rendered = match renderer {
+        TableCellRenderer::Link { url, text } => Some(render_link(url, text, value, columns, row)),
+        TableCellRenderer::Label { colors, default } => {
+            let text = cell_text(value).into_owned();
+            let color = colors.get(&text).copied().unwrap_or(*default);
+            Some(html! {
+                <Label label={ text } color={ label_color(color) } />
+            })
+        }
+        TableCellRenderer::Timestamp { relative } => render_timestamp(value, *relative),
+        TableCellRenderer::Bytes { binary } => render_bytes(value, *binary),
+        TableCellRenderer::Bool => render_bool(value),
+        TableCellRenderer::Json => Some(render_json(value)),
+        TableCellRenderer::Progress { min, max } => render_progress(value, *min, *max),
+    };
+    rendered.unwrap_or_else(|| html!(cell_text(value).into_owned()))
+}
+
+fn render_link(
+    url: &str,
+    text: &Option<String>,
+    value: &Value,
+    columns: &[String],
+    row: &[Value],
+) -> Html {
+    let href = TableCellRenderer::format_url(url, value, columns, row);
+    let text = match text {
+        Some(text) => TableCellRenderer::format(text, value, columns, row),
+        None => cell_text(value).into_owned(),
+    };
+
+    // NOTE: the other schemes, such as `javascript:`, are rendered as plain texts
+    if is_safe_href(&href) {
+        html! {
+            <a { href }>{ text }</a>
+        }
+    } else {
+        html!(text)
+    }
+}
+
+/// Return `true` if the link is either a relative one or a `http(s)` one.
+fn is_safe_href(href: &str) -> bool {
+    let href = href.trim_start();
+    match href.find([':', '/', '?', '#']) {
+        // NOTE: the scheme is terminated by the first colon
+        Some(index) if href[index..].starts_with(':') => {
+            let scheme = href[..index].to_ascii_lowercase();
+            scheme == "http" || scheme == "https"
+        }
+        _ => true,
+    }
+}
+
+fn render_timestamp(value: &Value, relative: bool) -> Option<Html> {
+    let timestamp = match value {
+        Value::String(value) => parse_datetime(value)?,
+        // NOTE: the numbers are regarded as UNIX timestamps in seconds
+        Value::Number(value) => DateTime::from_timestamp(value.as_i64()?, 0)?,
+        _ => return None,
+    };
+    let absolute = timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+
+    Some(if relative {
+        html! {
+            <span title={ absolute }>{ format_relative(timestamp, Utc::now()) }</span>
+        }
+    } else {
+        html! {
+            <span title={ timestamp.to_rfc3339() }>{ absolute }</span>
+        }
+    })
+}
+
+fn format_relative(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    const UNITS: [(i64, &str); 6] = [
+        (365 * 24 * 60 * 60, "year"),
+        (30 * 24 * 60 * 60, "month"),
+        (24 * 60 * 60, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ];
+
+    let seconds = (now - timestamp).num_seconds();
+    let Some((count, unit)) = UNITS
+        .iter()
+        .map(|&(size, unit)| (seconds.abs() / size, unit))
+        .find(|&(count, _)| count > 0)
+    else {
+        return "just now".into();
+    };
+
+    let plural = if count == 1 { "" } else { "s" };
+    if seconds > 0 {
+        format!("{count} {unit}{plural} ago")
+    } else {
+        format!("in {count} {unit}{plural}")
+    }
+}
+
+fn render_bytes(value: &Value, binary: bool) -> Option<Html> {
+    let value = cell_number(value).filter(|value| *value >= 0.0)?;
+    let unit = if binary {
+        UnitType::Binary
+    } else {
+        UnitType::Decimal
+    };
+    let size = Byte::from(value.round() as u64).get_appropriate_unit(unit);
+    Some(html!(format!("{size:.1}")))
+}
+
+fn render_bool(value: &Value) -> Option<Html> {
+    let value = match value {
+        Value::Bool(value) => *value,
+        Value::String(value) => parse_bool(value)?,
+        _ => return None,
+    };
+
+    let (icon, color) = if value {
+        (Icon::CheckCircle, "var(--pf-v5-global--success-color--100)")
+    } else {
+        (Icon::TimesCircle, "var(--pf-v5-global--danger-color--100)")
+    };
+    Some(html! {
+        <span title={ value.to_string() } style={ format!("color: {color};") }>
+            <i class={ icon.as_classes() }/>
+        </span>
+    })
+}
+
+fn render_json(value: &Value) -> Html {
+    let summary = match value {
+        Value::Array(values) => format!("[{} items]", values.len()),
+        Value::Object(fields) => format!("{{{} fields}}", fields.len()),
+        value => return html!(cell_text(value).into_owned()),
+    };
+    let code = ::serde_json::to_string_pretty(value).unwrap_or_default();
+
+    html! {
+        <Popover
+            target={ html! {
+                <Button variant={ ButtonVariant::Link }>{ summary }</Button>
+            } }
+            body={ html_nested! {
+                <PopoverBody>
+                    <CodeBlock>
+                        <CodeBlockCode>{ code }</CodeBlockCode>
+                    </CodeBlock>
+                </PopoverBody>
+            } }
+        />
+    }
+}
+
+fn render_progress(value: &Value, min: f64, max: f64) -> Option<Html> {
+    let value = cell_number(value)?;
+    let percent = if max > min {
+        ((value - min) / (max - min) * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    };
+
+    Some(html! {
+        <Progress value={ percent } />
+    })
+}
+
+const fn label_color(color: TableCellColor) -> Color {
+    match color {
+        TableCellColor::Blue => Color::Blue,
+        TableCellColor::Cyan => Color::Cyan,
+        TableCellColor::Gold => Color::Gold,
+        TableCellColor::Green => Color::Green,
+        TableCellColor::Grey => Color::Grey,
+        TableCellColor::Orange => Color::Orange,
+        TableCellColor::Purple => Color::Purple,
+        TableCellColor::Red => Color::Red,
+    }
+}
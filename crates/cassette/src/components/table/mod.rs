@@ -0,0 +1,1013 @@
+mod cell;
+
+use std::{collections::BTreeMap, rc::Rc};
+
+use cassette_core::cassette::{CassetteTaskHandle, GenericCassetteTaskHandle};
+use cassette_core::data::csv::CsvTable;
+use cassette_core::data::selection::{DataTableSelection, DataTableSelectionRepr};
+use cassette_core::data::table::{DataTableLog, DataTableLogKind, DataTableSource};
+use cassette_core::data::transform::{DataTableSortKey, DataTableSortOrder};
+use cassette_core::data::view::{
+    cell_text, DataTableColumnFilter, DataTableFilterKind, DataTableView,
+};
+use cassette_core::expr::{Expr, ExprContext, Template};
+use cassette_core::prelude::*;
+use cassette_core::{
+    cassette::CassetteContext,
+    components::builtin::table::{
+        SpecOptions, TableCellRenderer, TableInitialSelection, TableRowAction, TableSelectionMode,
+    },
+    data::table::DataTable,
+    task::{TaskResult, TaskSpec, TaskState},
+};
+use patternfly_yew::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
+use yew::prelude::*;
+use yew::virtual_dom::VChild;
+
+/// The initial height of each row in pixels, until the rendered rows are measured
+const ROW_HEIGHT: usize = 40;
+
+/// The number of the rows shown at once
+const VIEWPORT_ROWS: usize = 12;
+
+/// The number of the extra rows rendered above and below the viewport
+const OVERSCAN_ROWS: usize = 8;
+
+/// The outputs of the table, which are stored as the task state.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    /// The selected rows, or the whole table if the rows are not selectable
+    #[serde(flatten)]
+    table: Rc<DataTable>,
+
+    /// The search, the filters and the sorting of the table, without the matched rows
+    view: DataTableView,
+
+    /// The first selected row, such as the master of the master-detail views
+    row: Option<Map<String, Value>>,
+
+    /// The last clicked row action
+    action: Option<TableRowEvent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TableRowEvent {
+    name: String,
+    index: usize,
+    row: Map<String, Value>,
+}
+
+pub fn render(ctx: CassetteContext, spec: &TaskSpec) -> TaskResult<()> {
+    // NOTE: the input table is evaluated apart from the options, as it may be large
+    let mut spec = spec.0.clone();
+    let table = spec
+        .as_object_mut()
+        .and_then(|map| map.remove("table"))
+        .unwrap_or_default();
+    let SpecOptions {
+        actions,
+        initial_selection,
+        label_bulk_select,
+        renderers,
+        selection,
+        view: default_view,
+    } = ctx.eval(&spec).and_then(|spec| {
+        ::serde_json::from_value(spec)
+            .map_err(|error| format!("Failed to parse task spec: {error}"))
+    })?;
+
+    selection.check_initial_selection(initial_selection)?;
+
+    let input = use_input_table(&ctx, &table)?;
+    let DataTable { name, data, log } = &*input;
+    let columns = match data.columns() {
+        Ok(columns) => columns,
+        Err(error) => {
+            return Ok(TaskState::Break {
+                body: html! { <Error msg={ error.to_string() } /> },
+                state: (),
+            })
+        }
+    };
+    let types = match data.column_types() {
+        Ok(types) => types,
+        Err(error) => {
+            return Ok(TaskState::Break {
+                body: html! { <Error msg={ error.to_string() } /> },
+                state: (),
+            })
+        }
+    };
+    let records = match data.records() {
+        Ok(records) => records,
+        Err(error) => {
+            return Ok(TaskState::Break {
+                body: html! { <Error msg={ error.to_string() } /> },
+                state: (),
+            })
+        }
+    };
+
+    let view = ctx.use_persistent_state("view", false, || default_view);
+    // NOTE: the view determines the matched rows, which is much cheaper to hash
+    let filtered_log = match DataTableLog::derive(DataTableLogKind::Filtered, [log], view.get()) {
+        Ok(log) => log,
+        Err(error) => {
+            return Ok(TaskState::Break {
+                body: html! { <Error msg={ error.to_string() } /> },
+                state: (),
+            })
+        }
+    };
+
+    // NOTE: filtering and sorting the large tables are expensive,
+    //       so they are done only if the input or the view has been changed,
+    //       and the matched rows are kept out of the task state
+    let cached_rows = ctx.use_state("rows", false, || None::<(Uuid, Rc<Vec<usize>>)>);
+    let rows = match cached_rows.get() {
+        Some((id, rows)) if *id == filtered_log.id => rows.clone(),
+        _ => {
+            let rows = Rc::new(view.rows(&columns, &records));
+            cached_rows
+                .clone()
+                .lazy()
+                .set(Some((filtered_log.id, rows.clone())));
+            rows
+        }
+    };
+
+    let num_records = records.len();
+    let initial_selections = || {
+        let mut values = DataTableSelection::new(num_records);
+        match (selection, initial_selection) {
+            (TableSelectionMode::None, _) | (_, TableInitialSelection::None) => (),
+            (_, TableInitialSelection::All) => values.set_all(true),
+            (_, TableInitialSelection::First) => {
+                if let Some(&index) = rows.first() {
+                    values.set(index, true);
+                }
+            }
+        }
+        values
+    };
+
+    let force_init = false;
+    // NOTE: the persisted selections may be crafted, so they are clamped to the input
+    let selections = ctx.use_persistent_state_with(
+        "select",
+        force_init,
+        initial_selections,
+        |repr: DataTableSelectionRepr| repr.into_selection(num_records),
+    );
+    let action = ctx.use_state("action", force_init, || None::<TableRowEvent>);
+
+    // NOTE: the outputs are cached out of the task state,
+    //       so that they are not decoded on every render
+    let outputs = ctx.use_state("outputs", force_init, || None::<Rc<State>>);
+    let previous = outputs.get().clone();
+    let is_changed = previous
+        .as_ref()
+        .is_some_and(|previous| !previous.table.log.is_derived_from(log));
+
+    // NOTE: the selections and actions of the old input are meaningless,
+    //       and the persisted selections may be outdated,
+    //       so they are corrected here and stored by the component after rendering
+    let selected = if is_changed {
+        Rc::new(initial_selections())
+    } else if selections.len() != num_records {
+        let mut values = selections.get().clone();
+        values.resize(num_records);
+        Rc::new(values)
+    } else {
+        (*selections).clone()
+    };
+    let reset_action = is_changed && action.is_some();
+    let last_action = if reset_action {
+        None
+    } else {
+        action.get().clone()
+    };
+
+    // NOTE: the selections determine the selected table, as the view does
+    let selected_key = match selection {
+        TableSelectionMode::None => None,
+        TableSelectionMode::Single | TableSelectionMode::Multi => Some(&*selected),
+    };
+    let selected_log = match DataTableLog::derive(DataTableLogKind::Selected, [log], &selected_key)
+    {
+        Ok(log) => log,
+        Err(error) => {
+            return Ok(TaskState::Break {
+                body: html! { <Error msg={ error.to_string() } /> },
+                state: (),
+            })
+        }
+    };
+
+    let (table, row) = match &previous {
+        Some(previous) if previous.table.log.id == selected_log.id => {
+            (previous.table.clone(), previous.row.clone())
+        }
+        previous => {
+            let selected = match selection {
+                TableSelectionMode::None => records.clone(),
+                TableSelectionMode::Single | TableSelectionMode::Multi => Rc::new(
+                    selected
+                        .iter()
+                        .filter_map(|index| records.get(index).cloned())
+                        .collect(),
+                ),
+            };
+            let selected_data = DataTableSource::Csv(CsvTable {
+                headers: columns.clone(),
+                types,
+                records: selected,
+            });
+            let row = match selection {
+                TableSelectionMode::None => None,
+                TableSelectionMode::Single | TableSelectionMode::Multi => {
+                    match selected_data.first_row_as_json() {
+                        Ok(row) => row,
+                        Err(error) => {
+                            return Ok(TaskState::Break {
+                                body: html! { <Error msg={ error.to_string() } /> },
+                                state: (),
+                            })
+                        }
+                    }
+                }
+            };
+            let table = Rc::new(DataTable {
+                name: name.clone(),
+                data: Rc::new(selected_data),
+                log: selected_log.follow(previous.as_ref().map(|previous| &previous.table.log)),
+            });
+            (table, row)
+        }
+    };
+
+    let body = html! {
+        <Inner
+            action={ action.clone() }
+            { actions }
+            { columns }
+            { label_bulk_select }
+            log={ log.clone() }
+            name={ name.clone() }
+            records={ Shared(records) }
+            { renderers }
+            rows={ Shared(rows) }
+            selected={ Shared(selected) }
+            { selection }
+            { selections }
+            { reset_action }
+            view={ view.clone() }
+        />
+    };
+
+    let state = State {
+        table,
+        view: view.get().clone(),
+        row,
+        action: last_action,
+    };
+
+    // NOTE: the task state is encoded only if the outputs have been changed,
+    //       as encoding and comparing the selected table are proportional to its rows
+    let is_stored = previous.as_ref().is_some_and(|previous| {
+        previous.table.log.id == state.table.log.id
+            && previous.view == state.view
+            && previous.row == state.row
+            && previous.action == state.action
+    }) && {
+        let key = format!("/{}/log/id", escape_pointer(&ctx.task().name));
+        ExprContext::get_data(&ctx, &key).ok()
+            == Some(Value::String(state.table.log.id.to_string()))
+    };
+    if !is_stored {
+        let value = ::serde_json::to_value(&state)
+            .map(TaskSpec)
+            .map_err(|error| format!("Failed to encode task state: {error}"))?;
+        outputs.lazy().set(Some(Rc::new(state)));
+        ctx.set_task_state(value);
+    }
+
+    Ok(TaskState::Continue { body, state: () })
+}
+
+/// Evaluate the input table, reusing the last one if it has not been changed.
+fn use_input_table(ctx: &CassetteContext, table: &Value) -> Result<Rc<DataTable>, String> {
+    let cached = ctx.use_state("table", false, || None::<Rc<DataTable>>);
+
+    // NOTE: a pointer to another task can be checked by its log id alone,
+    //       without evaluating and decoding the whole table
+    let pointer = match table.as_str().map(str::parse::<Template>) {
+        Some(Ok(Template::Expr(Expr::Pointer { scope, key }))) => Some(Expr::Pointer {
+            scope,
+            key: format!("{key}/log/id"),
+        }),
+        _ => None,
+    };
+    if let (Some(pointer), Some(cached)) = (&pointer, cached.get()) {
+        if pointer.eval(ctx).ok() == Some(Value::String(cached.log.id.to_string())) {
+            return Ok(cached.clone());
+        }
+    }
+
+    let table: DataTable = ctx.eval(table).and_then(|table| {
+        ::serde_json::from_value(table)
+            .map_err(|error| format!("Failed to parse the input table: {error}"))
+    })?;
+    let table = Rc::new(table);
+    if pointer.is_some() {
+        cached.lazy().set(Some(table.clone()));
+    }
+    Ok(table)
+}
+
+/// Escape the task name as a segment of the JSON pointer.
+///
+/// Please see: https://datatracker.ietf.org/doc/html/rfc6901#section-3
+fn escape_pointer(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+struct Props {
+    action: CassetteTaskHandle<Option<TableRowEvent>>,
+    actions: Vec<TableRowAction>,
+    columns: Vec<String>,
+    label_bulk_select: String,
+    log: DataTableLog,
+    name: String,
+    records: Shared<Vec<Vec<Value>>>,
+    renderers: BTreeMap<String, TableCellRenderer>,
+    rows: Shared<Vec<usize>>,
+    /// The corrected selections, which may be newer than the stored ones
+    selected: Shared<DataTableSelection>,
+    selection: TableSelectionMode,
+    selections: CassetteTaskHandle<DataTableSelection>,
+    /// Whether the last row action belongs to the old input
+    reset_action: bool,
+    view: CassetteTaskHandle<DataTableView>,
+}
+
+#[function_component(Inner)]
+fn inner(props: &Props) -> Html {
+    let Props {
+        action,
+        actions,
+        columns,
+        label_bulk_select,
+        log,
+        name,
+        records,
+        renderers,
+        rows,
+        selected,
+        selection,
+        selections,
+        reset_action,
+        view,
+    } = props;
+
+    // NOTE: the handles should not be updated while rendering
+    use_effect_with(
+        (
+            action.clone(),
+            selected.clone(),
+            selections.clone(),
+            *reset_action,
+        ),
+        |(action, selected, selections, reset_action)| {
+            if *selections.get() != **selected {
+                selections.set((**selected).clone());
+            }
+            if *reset_action {
+                action.set(None);
+            }
+        },
+    );
+
+    let chip_name = format!("Name: {name}");
+    let chip_source = format!("Source: {} (v{})", log.kind, log.version);
+    let chip_updated_at = format!("Updated At: {}", log.updated_at.to_rfc3339());
+    let chip_rows = format!("Rows: {} / {}", rows.len(), records.len());
+
+    let header = Column::build_headers(columns, *selection, !actions.is_empty(), view);
+
+    // NOTE: the radio buttons of the other tables should not be grouped together
+    let group = use_memo((), |()| Uuid::new_v4().to_string());
+    let context = Rc::new(EntryContext {
+        action: action.clone(),
+        actions: actions.clone(),
+        columns: columns.clone(),
+        group: (*group).clone(),
+        records: records.clone(),
+        renderers: columns
+            .iter()
+            .map(|column| renderers.get(column).cloned())
+            .collect(),
+        selected: selected.clone(),
+        selection: *selection,
+        selections: selections.clone(),
+    });
+
+    let is_all_selected = !rows.is_empty() && rows.iter().all(|&index| selected.contains(index));
+    let onselectall = {
+        let rows = rows.clone();
+        let selected = selected.clone();
+        let selections = selections.clone();
+        Callback::from(move |state: CheckboxState| {
+            // NOTE: only the rows matched by the view are (de)selected
+            let mut values = (*selected).clone();
+            values.set_many(rows.iter().copied(), state.into());
+            selections.set(values)
+        })
+    };
+
+    let bulk_select = match selection {
+        TableSelectionMode::None | TableSelectionMode::Single => None,
+        TableSelectionMode::Multi => Some(html! {
+            <ToolbarItem r#type={ ToolbarItemType::BulkSelect }>
+                <Checkbox
+                    checked={
+                        if is_all_selected {
+                            CheckboxState::Checked
+                        } else {
+                            CheckboxState::Unchecked
+                        }
+                    }
+                    label={ label_bulk_select.clone() }
+                    onchange={ onselectall }
+                />
+            </ToolbarItem>
+        }),
+    };
+
+    let onsearch = {
+        let view = view.clone();
+        Callback::from(move |search: String| {
+            if view.search != search {
+                let mut updated = view.get().clone();
+                updated.search = search;
+                view.set(updated)
+            }
+        })
+    };
+
+    let filters = view.filters.iter().enumerate().map(|(index, filter)| {
+        let onclose = {
+            let view = view.clone();
+            Callback::from(move |()| {
+                let mut updated = view.get().clone();
+                updated.filters.remove(index);
+                view.set(updated)
+            })
+        };
+        html_nested! {
+            <Chip
+                text={ filter.to_string() }
+                { onclose }
+            />
+        }
+    });
+
+    // NOTE: only the rows near the scroll position are rendered,
+    //       and the others are replaced with the spacers of the measured height
+    let first = use_state_eq(|| 0);
+    let row_height = use_state_eq(|| ROW_HEIGHT);
+    let onscroll = {
+        let first = first.clone();
+        let row_height = *row_height;
+        Callback::from(move |event: Event| {
+            let target: Element = event.target_unchecked_into();
+            first.set(target.scroll_top().max(0) as usize / row_height)
+        })
+    };
+
+    let total_rows = rows.len();
+    let start = first.saturating_sub(OVERSCAN_ROWS).min(total_rows);
+    let end = (*first + VIEWPORT_ROWS + OVERSCAN_ROWS).min(total_rows);
+
+    // NOTE: the rows may be taller than the estimation, such as the wrapped texts,
+    //       so the spacers follow the rendered rows to keep the scroll position
+    let viewport = use_node_ref();
+    {
+        let viewport = viewport.clone();
+        let row_height = row_height.clone();
+        let spacers = (start > 0, end < total_rows);
+        use_effect(move || {
+            if let Some(height) = viewport
+                .cast::<Element>()
+                .and_then(|viewport| measure_row_height(&viewport, spacers))
+            {
+                row_height.set(height)
+            }
+        });
+    }
+
+    let entries = use_memo(
+        (start, end, *row_height, rows.clone(), context),
+        |(start, end, row_height, rows, context)| {
+            let spacer = |num_rows| Entry::Spacer {
+                height: num_rows * row_height,
+            };
+
+            let mut entries = Vec::with_capacity(end - start + 2);
+            if *start > 0 {
+                entries.push(spacer(*start));
+            }
+            entries.extend(rows[*start..*end].iter().map(|&index| Entry::Row {
+                index,
+                context: context.clone(),
+            }));
+            if *end < rows.len() {
+                entries.push(spacer(rows.len() - end));
+            }
+            entries
+        },
+    );
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    html! (
+        <>
+            <Toolbar>
+                <ToolbarContent>
+                    { bulk_select }
+                    <ToolbarItem>
+                        <TextInputGroup>
+                            <TextInputGroupMain
+                                onchange={ onsearch }
+                                placeholder="Search"
+                                value={ view.search.clone() }
+                            />
+                        </TextInputGroup>
+                    </ToolbarItem>
+                    <FilterBuilder
+                        columns={ columns.clone() }
+                        view={ view.clone() }
+                    />
+                    <ToolbarItem r#type={ ToolbarItemType::ChipGroup }>
+                        <ChipGroup>
+                            <Chip
+                                text={ chip_name }
+                            />
+                            <Chip
+                                text={ chip_source }
+                            />
+                            <Chip
+                                text={ chip_updated_at }
+                            />
+                            <Chip
+                                text={ chip_rows }
+                            />
+                        </ChipGroup>
+                    </ToolbarItem>
+                </ToolbarContent>
+                <ToolbarContent>
+                    <ToolbarItem r#type={ ToolbarItemType::ChipGroup }>
+                        <ChipGroup>
+                            { for filters }
+                        </ChipGroup>
+                    </ToolbarItem>
+                    <ColumnToggles
+                        columns={ columns.clone() }
+                        view={ view.clone() }
+                    />
+                </ToolbarContent>
+            </Toolbar>
+            <div
+                class="cassette-table-viewport"
+                { onscroll }
+                ref={ viewport }
+                style={ format!("max-height: {}px;", (VIEWPORT_ROWS + 1) * *row_height) }
+            >
+                <Table<Column, UseTableData<Column, MemoizedTableModel<Entry>>>
+                    mode={ TableMode::Compact }
+                    { header }
+                    { entries }
+                />
+            </div>
+        </>
+    )
+}
+
+/// Measure the mean height of the rendered rows, skipping the leading and the trailing spacers.
+fn measure_row_height(viewport: &Element, (leading, trailing): (bool, bool)) -> Option<usize> {
+    let rows = viewport.query_selector_all("tbody > tr").ok()?;
+    let start = u32::from(leading);
+    let end = rows.length().checked_sub(u32::from(trailing))?;
+
+    let (total, count) = (start..end)
+        .filter_map(|index| rows.item(index)?.dyn_into::<HtmlElement>().ok())
+        .map(|row| row.offset_height().max(0) as usize)
+        .fold((0, 0), |(total, count), height| (total + height, count + 1));
+    (count > 0 && total > 0).then(|| (total + count / 2) / count)
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+struct ViewProps {
+    columns: Vec<String>,
+    view: CassetteTaskHandle<DataTableView>,
+}
+
+#[function_component(FilterBuilder)]
+fn filter_builder(props: &ViewProps) -> Html {
+    let ViewProps { columns, view } = props;
+
+    let column = use_state_eq(|| None::<String>);
+    let kind = use_state_eq(DataTableFilterKind::default);
+    let value = use_state_eq(String::default);
+
+    let filter = (*column)
+        .clone()
+        .filter(|_| !value.is_empty())
+        .zip(kind.parse(&value))
+        .map(|(column, op)| DataTableColumnFilter { column, op });
+
+    let onchange_column = {
+        let column = column.clone();
+        Callback::from(move |updated: Option<String>| column.set(updated))
+    };
+    let onchange_kind = {
+        let kind = kind.clone();
+        Callback::from(move |updated: Option<String>| {
+            kind.set(
+                updated
+                    .and_then(|updated| updated.parse().ok())
+                    .unwrap_or_default(),
+            )
+        })
+    };
+    let onchange_value = {
+        let value = value.clone();
+        Callback::from(move |updated: String| value.set(updated))
+    };
+    let onclick = {
+        let filter = filter.clone();
+        let value = value.clone();
+        let view = view.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(filter) = filter.clone() {
+                let mut updated = view.get().clone();
+                updated.filters.push(filter);
+                view.set(updated);
+                value.set(String::default());
+            }
+        })
+    };
+
+    let columns = columns.iter().map(|column| {
+        html_nested! {
+            <FormSelectOption<String>
+                id={ format!("column/{column}") }
+                value={ column.clone() }
+            />
+        }
+    });
+    let kinds = DataTableFilterKind::ALL.iter().map(|kind| {
+        html_nested! {
+            <FormSelectOption<String>
+                id={ format!("kind/{kind}") }
+                value={ kind.to_string() }
+            />
+        }
+    });
+    let placeholder = match *kind {
+        DataTableFilterKind::Range => "min..max",
+        DataTableFilterKind::Contains | DataTableFilterKind::Equals => "Value",
+    };
+
+    html! {
+        <>
+            <ToolbarItem>
+                <FormSelect<String>
+                    onchange={ onchange_column }
+                    placeholder="Column"
+                    value={ (*column).clone() }
+                >
+                    { for columns }
+                </FormSelect<String>>
+            </ToolbarItem>
+            <ToolbarItem>
+                <FormSelect<String>
+                    onchange={ onchange_kind }
+                    value={ kind.to_string() }
+                >
+                    { for kinds }
+                </FormSelect<String>>
+            </ToolbarItem>
+            <ToolbarItem>
+                <TextInputGroup>
+                    <TextInputGroupMain
+                        onchange={ onchange_value }
+                        { placeholder }
+                        value={ (*value).clone() }
+                    />
+                </TextInputGroup>
+            </ToolbarItem>
+            <ToolbarItem>
+                <Button
+                    disabled={ filter.is_none() }
+                    { onclick }
+                    variant={ ButtonVariant::Secondary }
+                >
+                    { "Add Filter" }
+                </Button>
+            </ToolbarItem>
+        </>
+    }
+}
+
+#[function_component(ColumnToggles)]
+fn column_toggles(props: &ViewProps) -> Html {
+    let ViewProps { columns, view } = props;
+
+    let toggles = columns.iter().map(|column| {
+        let onchange = {
+            let column = column.clone();
+            let view = view.clone();
+            Callback::from(move |state: CheckboxState| {
+                let mut updated = view.get().clone();
+                if state.into() {
+                    updated.hidden.remove(&column);
+                } else {
+                    updated.hidden.insert(column.clone());
+                }
+                view.set(updated)
+            })
+        };
+        html! {
+            <ToolbarItem>
+                <Checkbox
+                    checked={
+                        if view.hidden.contains(column) {
+                            CheckboxState::Unchecked
+                        } else {
+                            CheckboxState::Checked
+                        }
+                    }
+                    label={ column.clone() }
+                    { onchange }
+                />
+            </ToolbarItem>
+        }
+    });
+
+    html! {
+        <>
+            { for toggles }
+        </>
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Column {
+    Select,
+    Value(usize),
+    Actions,
+}
+
+impl Column {
+    fn build_headers(
+        columns: &[String],
+        selection: TableSelectionMode,
+        has_actions: bool,
+        view: &CassetteTaskHandle<DataTableView>,
+    ) -> VChild<TableHeader<Self>> {
+        let sortby = view.sort.as_ref().and_then(|sort| {
+            let index = Self::Value(columns.iter().position(|column| *column == sort.column)?);
+            Some(match sort.order {
+                DataTableSortOrder::Asc => TableHeaderSortBy::ascending(index),
+                DataTableSortOrder::Desc => TableHeaderSortBy::descending(index),
+            })
+        });
+        let onsort = {
+            let columns = columns.to_vec();
+            let view = view.clone();
+            Callback::from(move |sortby: TableHeaderSortBy<Self>| {
+                if let Self::Value(index) = sortby.index {
+                    let mut updated = view.get().clone();
+                    updated.sort = Some(DataTableSortKey {
+                        column: columns[index].clone(),
+                        order: match sortby.order {
+                            Order::Ascending => DataTableSortOrder::Asc,
+                            Order::Descending => DataTableSortOrder::Desc,
+                        },
+                    });
+                    view.set(updated)
+                }
+            })
+        };
+
+        let headers = view.columns(columns).into_iter().map(|index| {
+            html_nested! {
+                <TableColumn<Self>
+                    index={ Self::Value(index) }
+                    label={ columns[index].clone() }
+                    sortby={ sortby.clone() }
+                    onsort={ onsort.clone() }
+                />
+            }
+        });
+
+        let select = match selection {
+            TableSelectionMode::None => None,
+            TableSelectionMode::Single | TableSelectionMode::Multi => Some(html_nested! {
+                <TableColumn<Self>
+                    index={ Self::Select }
+                />
+            }),
+        };
+        let actions = has_actions.then(|| {
+            html_nested! {
+                <TableColumn<Self>
+                    index={ Self::Actions }
+                />
+            }
+        });
+
+        html_nested! {
+            <TableHeader<Self>>
+                { for select }
+                { for headers }
+                { for actions }
+            </TableHeader<Self>>
+        }
+    }
+}
+
+#[derive(PartialEq)]
+struct EntryContext {
+    action: CassetteTaskHandle<Option<TableRowEvent>>,
+    actions: Vec<TableRowAction>,
+    columns: Vec<String>,
+    group: String,
+    records: Shared<Vec<Vec<Value>>>,
+    /// The renderers of the columns, in the same order
+    renderers: Vec<Option<TableCellRenderer>>,
+    selected: Shared<DataTableSelection>,
+    selection: TableSelectionMode,
+    selections: CassetteTaskHandle<DataTableSelection>,
+}
+
+#[derive(Clone)]
+enum Entry {
+    /// The rows which are out of the viewport
+    Spacer { height: usize },
+    Row {
+        index: usize,
+        context: Rc<EntryContext>,
+    },
+}
+
+impl Entry {
+    fn render_select(index: usize, context: &EntryContext) -> Html {
+        let EntryContext {
+            group,
+            selected,
+            selection,
+            selections,
+            ..
+        } = context;
+
+        match selection {
+            TableSelectionMode::None => Html::default(),
+            TableSelectionMode::Single => html! {
+                <Radio
+                    checked={ selected.contains(index) }
+                    name={ group.clone() }
+                    onchange={
+                        let num_records = selected.len();
+                        let selections = selections.clone();
+                        Callback::from(move |()| {
+                            let mut values = DataTableSelection::new(num_records);
+                            values.set(index, true);
+                            selections.set(values)
+                        })
+                    }
+                />
+            },
+            TableSelectionMode::Multi => html! {
+                <Checkbox
+                    checked={
+                        if selected.contains(index) {
+                            CheckboxState::Checked
+                        } else {
+                            CheckboxState::Unchecked
+                        }
+                    }
+                    onchange={
+                        let selected = selected.clone();
+                        let selections = selections.clone();
+                        Callback::from(move |state: CheckboxState| {
+                            let mut values = (*selected).clone();
+                            values.set(index, state.into());
+                            selections.set(values)
+                        })
+                    }
+                />
+            },
+        }
+    }
+
+    fn render_actions(index: usize, context: &EntryContext) -> Html {
+        let EntryContext {
+            action,
+            actions,
+            columns,
+            records,
+            ..
+        } = context;
+
+        let buttons = actions.iter().map(|TableRowAction { name, label }| {
+            let onclick = {
+                let action = action.clone();
+                let event = TableRowEvent {
+                    name: name.clone(),
+                    index,
+                    row: columns
+                        .iter()
+                        .cloned()
+                        .zip(records[index].iter().cloned())
+                        .collect(),
+                };
+                Callback::from(move |_: MouseEvent| action.set(Some(event.clone())))
+            };
+            html! {
+                <Button { onclick } variant={ ButtonVariant::Link }>
+                    { label.clone().unwrap_or_else(|| name.clone()) }
+                </Button>
+            }
+        });
+
+        html! { <>{ for buttons }</> }
+    }
+
+    fn render_value(index: usize, context: &EntryContext, column: usize) -> Html {
+        let EntryContext {
+            columns,
+            records,
+            renderers,
+            ..
+        } = context;
+
+        let row = &records[index];
+        let Some(value) = row.get(column) else {
+            return Html::default();
+        };
+        match renderers.get(column).and_then(Option::as_ref) {
+            Some(renderer) => cell::render(renderer, value, columns, row),
+            None => html!(cell_text(value).into_owned()),
+        }
+    }
+}
+
+impl TableEntryRenderer<Column> for Entry {
+    fn render_cell(&self, cell: CellContext<'_, Column>) -> Cell {
+        match self {
+            Self::Spacer { height } => html! {
+                <div style={ format!("height: {height}px;") } />
+            },
+            Self::Row { index, context } => match cell.column {
+                Column::Select => Self::render_select(*index, context),
+                Column::Actions => Self::render_actions(*index, context),
+                Column::Value(column) => Self::render_value(*index, context, *column),
+            },
+        }
+        .into()
+    }
+}
+
+/// A shared value, which is compared by its address
+/// as it may be too large to compare by its contents.
+#[derive(Debug)]
+struct Shared<T>(Rc<T>);
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> PartialEq for Shared<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> ::std::ops::Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -1,6 +1,6 @@
 use cassette_core::{
     cassette::CassetteContext,
-    components::ComponentRenderer,
+    components::{builtin::text::Spec, ComponentRenderer},
     task::{TaskResult, TaskState},
 };
 use patternfly_yew::prelude::*;
@@ -9,15 +9,6 @@ use serde_json::Value;
 use yew::prelude::*;
 use yew_markdown::Markdown;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    msg: Value,
-
-    #[serde(default)]
-    progress: bool,
-}
-
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct State {}
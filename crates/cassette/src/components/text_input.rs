@@ -1,6 +1,6 @@
 use cassette_core::{
     cassette::{CassetteContext, GenericCassetteTaskHandle},
-    components::ComponentRenderer,
+    components::{builtin::text_input::Spec, ComponentRenderer},
     keycode::KEYCODE_ENTER,
     task::{TaskResult, TaskState},
 };
@@ -8,25 +8,6 @@ use patternfly_yew::prelude::*;
 use serde::{Deserialize, Serialize};
 use yew::{html::IntoPropValue, prelude::*};
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Properties)]
-#[serde(rename_all = "camelCase")]
-pub struct Spec {
-    #[serde(default)]
-    default: Option<String>,
-    #[serde(default)]
-    label: Option<String>,
-    #[serde(default = "Spec::default_label_submit")]
-    label_submit: String,
-    #[serde(default)]
-    placeholder: Option<String>,
-}
-
-impl Spec {
-    fn default_label_submit() -> String {
-        "Submit".into()
-    }
-}
-
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct State {
@@ -45,7 +26,7 @@ impl ComponentRenderer<Spec> for State {
         let handler_name = "text";
         let force_init = false;
         let text = ctx
-            .use_state(handler_name, force_init, || default.unwrap_or_default())
+            .use_persistent_state(handler_name, force_init, || default.unwrap_or_default())
             .lazy();
 
         let onchange = {
@@ -0,0 +1,33 @@
+use cassette_core::{
+    cassette::CassetteContext,
+    components::builtin::transform::Spec,
+    data::table::DataTable,
+    task::{TaskResult, TaskSpec, TaskState},
+};
+use serde_json::Value;
+
+pub fn render(ctx: CassetteContext, spec: &TaskSpec) -> TaskResult<()> {
+    // NOTE: the filter is evaluated per row, so it is excluded here
+    let mut spec = spec.0.clone();
+    let filter = spec.as_object_mut().and_then(|map| map.remove("filter"));
+    let mut spec = ctx.eval(&spec)?;
+    if let (Some(filter), Value::Object(map)) = (filter, &mut spec) {
+        map.insert("filter".into(), filter);
+    }
+
+    let Spec { table, transform } = ::serde_json::from_value(spec)
+        .map_err(|error| format!("Failed to parse task spec: {error}"))?;
+
+    let previous: Option<DataTable> = ctx.get_child().ok().flatten();
+    let table = transform
+        .apply(&ctx, &table, previous.as_ref())
+        .map_err(|error| format!("Failed to transform the table: {error}"))?;
+
+    let state = ::serde_json::to_value(table)
+        .map(TaskSpec)
+        .map_err(|error| format!("Failed to encode task state: {error}"))?;
+
+    Ok(TaskState::Skip {
+        state: ctx.set_task_state(state),
+    })
+}
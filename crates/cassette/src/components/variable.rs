@@ -4,7 +4,12 @@ use cassette_core::{
 };
 
 pub fn render(ctx: CassetteContext, spec: &TaskSpec) -> TaskResult<()> {
+    // NOTE: the variables can be overridden by the URL query
+    let value = ctx
+        .get_persistent_state("value")
+        .unwrap_or_else(|| spec.clone());
+
     Ok(TaskState::Skip {
-        state: ctx.set_task_state(spec.clone()),
+        state: ctx.set_task_state(value),
     })
 }
@@ -0,0 +1,30 @@
+mod app;
+mod build_info;
+mod components;
+mod history;
+mod hooks;
+mod pages;
+mod panic_hook;
+mod route;
+mod tracer;
+
+pub use cassette_core::components::registry::TaskRegistry;
+use tracing::Level;
+
+pub use self::components::registry;
+
+const LOG_LEVEL: Level = if self::build_info::CI_PLATFORM.is_some() {
+    Level::TRACE
+} else if cfg!(debug_assertions) {
+    Level::DEBUG
+} else {
+    Level::INFO
+};
+
+/// Run the app, rendering the tasks with the given registry.
+pub fn run(registry: TaskRegistry) {
+    crate::tracer::init(LOG_LEVEL);
+    crate::panic_hook::init();
+    registry.install();
+    ::yew::Renderer::<crate::app::App>::new().render();
+}
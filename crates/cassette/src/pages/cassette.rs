@@ -1,8 +1,8 @@
 use cassette_core::{
     cassette::{Cassette as CassetteData, CassetteState},
+    components::registry::TaskRegistry,
     net::fetch::FetchState,
     prelude::*,
-    task::{TaskRenderer, TaskState},
 };
 use patternfly_yew::prelude::*;
 use tracing::info;
@@ -10,7 +10,7 @@ use uuid::Uuid;
 use yew::prelude::*;
 
 use crate::{
-    components::RootCassetteTask,
+    components::RootCassetteTasks,
     history::{History, HistoryLog},
     hooks::gateway::use_cassette,
     pages::error::ErrorKind,
@@ -66,42 +66,38 @@ fn cassette_data(props: &DataProps) -> Html {
     let title = data.title();
     let subtitle = data.description.clone();
 
-    let mut contents = vec![];
-    {
+    let diagnostics = TaskRegistry::current()
+        .validator()
+        .validate(&data.component);
+    let warnings = if diagnostics.is_empty() {
+        None
+    } else {
+        Some(html! {
+            <Alert
+                inline=true
+                title="Invalid tasks"
+                r#type={AlertType::Warning}
+            >
+                <ul>
+                    { for diagnostics.into_iter().map(|diagnostic| html! {
+                        <li>
+                            <code>{ diagnostic.path }</code>
+                            { ": " }
+                            { diagnostic.message }
+                        </li>
+                    }) }
+                </ul>
+            </Alert>
+        })
+    };
+
+    let contents = {
         let trigger = use_force_update();
         let mut root_state = CassetteState::new(data.id, trigger);
 
-        for task in data.component.tasks.iter().map(RootCassetteTask) {
-            match task.render(&mut root_state) {
-                Ok(TaskState::Break { body, state: _ }) => {
-                    contents.push(body);
-                    break;
-                }
-                Ok(TaskState::Continue { body, state: _ }) => {
-                    contents.push(body);
-                    continue;
-                }
-                Ok(TaskState::Skip { state: _ }) => {
-                    continue;
-                }
-                Err(error) => {
-                    let body = html! {
-                        <Alert
-                            inline=true
-                            title="Error"
-                            r#type={AlertType::Danger}
-                        >
-                            <p style="white-space: pre-line;">
-                                { error }
-                            </p>
-                        </Alert>
-                    };
-                    contents.push(body);
-                    break;
-                }
-            }
-        }
-    }
+        let (contents, _) = RootCassetteTasks(&data.component.tasks).render(&mut root_state);
+        warnings.into_iter().chain(contents)
+    };
 
     html! {
         <super::PageBody { title } { subtitle } >